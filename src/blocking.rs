@@ -0,0 +1,297 @@
+//! A synchronous client, running each call of the async [`crate::Cloudinary`]
+//! to completion on its own runtime.
+//!
+//! Like `reqwest::blocking`, it must not be used from within an async runtime.
+//! ```rust,no_run
+//! use cloudinary::blocking::Cloudinary;
+//! use cloudinary::upload::{UploadOptions, UploadSource};
+//! let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+//! let result = cloudinary.upload(UploadSource::path("./image.png"), &UploadOptions::new());
+//! ```
+
+use bytes::Bytes;
+use core::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::result::{DeleteResponse, RenameResponse, UploadResponse};
+use crate::upload::{UploadOptions, UploadProgress, UploadSource};
+use crate::{ApiResponse, CloudinaryBuilder, CloudinaryError, DeliveryUrl, SignedUpload};
+
+#[derive(Clone)]
+pub struct Cloudinary {
+    inner: crate::Cloudinary,
+    runtime: Arc<Runtime>,
+}
+
+impl Cloudinary {
+    pub fn new(cloud_name: &str, api_key: i64, api_secret: &str) -> Self {
+        CloudinaryBuilder::new(cloud_name, api_key, api_secret)
+            .build()
+            .into()
+    }
+
+    /// See [`crate::Cloudinary::unsigned`].
+    pub fn unsigned(cloud_name: &str, upload_preset: &str) -> Self {
+        CloudinaryBuilder::unsigned(cloud_name, upload_preset)
+            .build()
+            .into()
+    }
+
+    /// See [`crate::Cloudinary::from_env`].
+    pub fn from_env() -> Result<Self, CloudinaryError> {
+        Ok(crate::Cloudinary::from_env()?.into())
+    }
+
+    /// See [`crate::Cloudinary::upload`].
+    pub fn upload(
+        &self,
+        source: UploadSource,
+        options: &UploadOptions<'_>,
+    ) -> Result<UploadResponse, CloudinaryError> {
+        self.runtime.block_on(self.inner.upload(source, options))
+    }
+
+    /// See [`crate::Cloudinary::upload_with_response`].
+    pub fn upload_with_response(
+        &self,
+        source: UploadSource,
+        options: &UploadOptions<'_>,
+    ) -> Result<ApiResponse<UploadResponse>, CloudinaryError> {
+        self.runtime
+            .block_on(self.inner.upload_with_response(source, options))
+    }
+
+    /// See [`crate::Cloudinary::upload_bytes`].
+    pub fn upload_bytes(
+        &self,
+        data: impl Into<Bytes>,
+        filename: &str,
+        options: &UploadOptions<'_>,
+    ) -> Result<UploadResponse, CloudinaryError> {
+        self.runtime
+            .block_on(self.inner.upload_bytes(data, filename, options))
+    }
+
+    /// See [`crate::Cloudinary::upload_image`].
+    pub fn upload_image(
+        &self,
+        file_path: &str,
+        options: &UploadOptions<'_>,
+    ) -> Result<UploadResponse, CloudinaryError> {
+        self.runtime
+            .block_on(self.inner.upload_image(file_path, options))
+    }
+
+    /// See [`crate::Cloudinary::upload_large`].
+    pub fn upload_large(
+        &self,
+        source: UploadSource,
+        options: &UploadOptions<'_>,
+        chunk_size: usize,
+    ) -> Result<UploadResponse, CloudinaryError> {
+        self.runtime
+            .block_on(self.inner.upload_large(source, options, chunk_size))
+    }
+
+    /// See [`crate::Cloudinary::upload_large_with_response`].
+    pub fn upload_large_with_response(
+        &self,
+        source: UploadSource,
+        options: &UploadOptions<'_>,
+        chunk_size: usize,
+    ) -> Result<ApiResponse<UploadResponse>, CloudinaryError> {
+        self.runtime.block_on(
+            self.inner
+                .upload_large_with_response(source, options, chunk_size),
+        )
+    }
+
+    /// See [`crate::Cloudinary::start_resumable_upload`].
+    pub fn start_resumable_upload(
+        &self,
+        file_path: impl Into<PathBuf>,
+        options: &UploadOptions<'_>,
+        chunk_size: usize,
+        journal_path: impl Into<PathBuf>,
+    ) -> Result<ResumableUpload, CloudinaryError> {
+        let inner = self.runtime.block_on(self.inner.start_resumable_upload(
+            file_path,
+            options,
+            chunk_size,
+            journal_path,
+        ))?;
+        Ok(self.resumable(inner))
+    }
+
+    /// See [`crate::Cloudinary::open_resumable_upload`].
+    pub fn open_resumable_upload(
+        &self,
+        journal_path: impl Into<PathBuf>,
+    ) -> Result<ResumableUpload, CloudinaryError> {
+        let inner = self
+            .runtime
+            .block_on(self.inner.open_resumable_upload(journal_path))?;
+        Ok(self.resumable(inner))
+    }
+
+    /// See [`crate::Cloudinary::rename_image`].
+    pub fn rename_image(
+        &self,
+        public_id: &str,
+        new_public_id: &str,
+    ) -> Result<RenameResponse, CloudinaryError> {
+        self.runtime
+            .block_on(self.inner.rename_image(public_id, new_public_id))
+    }
+
+    /// See [`crate::Cloudinary::rename_image_with_response`].
+    pub fn rename_image_with_response(
+        &self,
+        public_id: &str,
+        new_public_id: &str,
+    ) -> Result<ApiResponse<RenameResponse>, CloudinaryError> {
+        self.runtime.block_on(
+            self.inner
+                .rename_image_with_response(public_id, new_public_id),
+        )
+    }
+
+    /// See [`crate::Cloudinary::delete_image`].
+    pub fn delete_image(&self, public_id: &str) -> Result<DeleteResponse, CloudinaryError> {
+        self.runtime.block_on(self.inner.delete_image(public_id))
+    }
+
+    /// See [`crate::Cloudinary::delete_image_with_response`].
+    pub fn delete_image_with_response(
+        &self,
+        public_id: &str,
+    ) -> Result<ApiResponse<DeleteResponse>, CloudinaryError> {
+        self.runtime
+            .block_on(self.inner.delete_image_with_response(public_id))
+    }
+
+    /// See [`crate::Cloudinary::sign_upload`].
+    pub fn sign_upload(
+        &self,
+        options: &UploadOptions<'_>,
+    ) -> Result<SignedUpload, CloudinaryError> {
+        self.inner.sign_upload(options)
+    }
+
+    /// See [`crate::Cloudinary::delivery_url`].
+    pub fn delivery_url(&self, public_id: &str) -> DeliveryUrl<'_> {
+        self.inner.delivery_url(public_id)
+    }
+
+    fn resumable(&self, inner: crate::ResumableUpload) -> ResumableUpload {
+        ResumableUpload {
+            inner,
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// Wraps an async client, e.g. one configured with [`CloudinaryBuilder`].
+/// Panics if the runtime cannot be created.
+impl From<crate::Cloudinary> for Cloudinary {
+    fn from(inner: crate::Cloudinary) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create the runtime of the blocking client");
+        Self {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+/// Create connection options from URI `cloudinary://<apiKey>:<apiSecret>@<cloudName>`
+impl FromStr for Cloudinary {
+    type Err = CloudinaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse::<crate::Cloudinary>()?.into())
+    }
+}
+
+/// Like the async client, never shows the API secret.
+impl fmt::Debug for Cloudinary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// See [`crate::ResumableUpload`].
+pub struct ResumableUpload {
+    inner: crate::ResumableUpload,
+    runtime: Arc<Runtime>,
+}
+
+impl ResumableUpload {
+    pub fn unique_upload_id(&self) -> &str {
+        self.inner.unique_upload_id()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.inner.total_bytes()
+    }
+
+    pub fn bytes_uploaded(&self) -> u64 {
+        self.inner.bytes_uploaded()
+    }
+
+    /// See [`crate::ResumableUpload::set_progress`].
+    pub fn set_progress(&mut self, progress: impl Fn(UploadProgress) + Send + Sync + 'static) {
+        self.inner.set_progress(progress);
+    }
+
+    /// See [`crate::ResumableUpload::resume`].
+    pub fn resume(&mut self) -> Result<UploadResponse, CloudinaryError> {
+        self.runtime.block_on(self.inner.resume())
+    }
+
+    pub fn abandon(self) -> Result<(), CloudinaryError> {
+        self.runtime.block_on(self.inner.abandon())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cloudinary;
+    use crate::transport::fake::{self, FakeTransport};
+    use crate::upload::{UploadOptions, UploadSource};
+
+    #[test]
+    fn runs_calls_to_completion() {
+        let transport = FakeTransport::default();
+        let cloudinary: Cloudinary = fake::cloudinary(&transport).into();
+
+        let options = UploadOptions::new().set_public_id(String::from("sample"));
+        let response = cloudinary
+            .upload(UploadSource::bytes(vec![0u8; 10], "data.bin"), &options)
+            .unwrap();
+        assert_eq!(response.public_id, "sample");
+        let response = cloudinary.delete_image("sample").unwrap();
+        assert_eq!(response.result, "ok");
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].url,
+            "https://api.cloudinary.com/v1_1/cloud_name/image/upload"
+        );
+        assert_eq!(requests[0].file.as_ref().unwrap().data, [0u8; 10]);
+        assert_eq!(requests[1].field("public_id"), Some("sample"));
+    }
+
+    #[test]
+    fn debug_hides_secret() {
+        let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+        let debug = format!("{cloudinary:?}");
+        assert!(debug.contains("cloud_name"));
+        assert!(!debug.contains("api_secret"));
+    }
+}
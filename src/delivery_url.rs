@@ -0,0 +1,245 @@
+use crate::transformation::Transformation;
+use crate::upload::{DeliveryType, ResourceTypes};
+use crate::Cloudinary;
+
+const SHARED_CDN: &str = "res.cloudinary.com";
+
+/// Builds the URL delivering an asset:
+/// `https://res.cloudinary.com/{cloud}/{resource_type}/{type}/{transformation}/v{version}/{public_id}.{format}`.
+/// ```rust
+/// use cloudinary::Cloudinary;
+/// use cloudinary::transformation::{CropMode, Transformation};
+/// let cloudinary = Cloudinary::new("demo", 123456789, "api_secret");
+/// let url = cloudinary
+///     .delivery_url("sample")
+///     .set_transformation(Transformation::new().set_crop(CropMode::Fill).set_width(300))
+///     .set_format("jpg")
+///     .build();
+/// assert_eq!(url, "https://res.cloudinary.com/demo/image/upload/c_fill,w_300/sample.jpg");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeliveryUrl<'a> {
+    cloudinary: &'a Cloudinary,
+    public_id: String,
+    resource_type: ResourceTypes,
+    delivery_type: DeliveryType,
+    transformation: Option<Transformation>,
+    version: Option<u64>,
+    force_version: bool,
+    format: Option<String>,
+    secure: bool,
+}
+
+impl Cloudinary {
+    /// Starts the URL of an asset, over HTTPS unless the client is configured otherwise.
+    pub fn delivery_url(&self, public_id: &str) -> DeliveryUrl<'_> {
+        DeliveryUrl {
+            cloudinary: self,
+            public_id: public_id.to_string(),
+            resource_type: ResourceTypes::Image,
+            delivery_type: DeliveryType::Upload,
+            transformation: None,
+            version: None,
+            force_version: true,
+            format: None,
+            secure: self.secure,
+        }
+    }
+}
+
+impl DeliveryUrl<'_> {
+    /// Defaults to `image`.
+    pub fn set_resource_type(mut self, resource_type: ResourceTypes) -> Self {
+        self.resource_type = resource_type;
+        self
+    }
+
+    /// Defaults to `upload`. With `fetch`, the public_id is the URL of the remote asset.
+    pub fn set_delivery_type(mut self, delivery_type: DeliveryType) -> Self {
+        self.delivery_type = delivery_type;
+        self
+    }
+
+    /// Strings such as `"c_fill,w_300"` are used as is.
+    pub fn set_transformation(mut self, transformation: impl Into<Transformation>) -> Self {
+        self.transformation = Some(transformation.into());
+        self
+    }
+
+    /// Version of the asset, as returned by the upload, to bypass cached older versions.
+    pub fn set_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Whether public_ids in folders get the `v1` version when none is set,
+    /// so that the folder cannot be mistaken for a transformation. Defaults to `true`.
+    pub fn set_force_version(mut self, force_version: bool) -> Self {
+        self.force_version = force_version;
+        self
+    }
+
+    /// Extension of the delivered file, which Cloudinary converts the asset to.
+    pub fn set_format(mut self, format: &str) -> Self {
+        self.format = Some(format.to_string());
+        self
+    }
+
+    pub fn set_secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    pub fn build(&self) -> String {
+        let version = self.version.or_else(|| {
+            let needs_version = self.force_version
+                && self.public_id.contains('/')
+                && !is_versioned(&self.public_id)
+                && !is_remote(&self.public_id);
+            needs_version.then_some(1)
+        });
+
+        let mut public_id = escape(&self.public_id);
+        if let Some(format) = &self.format {
+            public_id = format!("{public_id}.{format}");
+        }
+
+        [
+            Some(self.prefix()),
+            Some(self.resource_type.to_string()),
+            Some(self.delivery_type.to_string()),
+            self.transformation
+                .as_ref()
+                .map(Transformation::to_string)
+                .filter(|t| !t.is_empty()),
+            version.map(|version| format!("v{version}")),
+            Some(public_id),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("/")
+    }
+
+    /// Scheme and host, followed by the cloud name unless it is part of the host.
+    fn prefix(&self) -> String {
+        let cloudinary = self.cloudinary;
+        let cloud_name = &cloudinary.cloud_name;
+        let private_cdn = cloudinary.private_cdn;
+
+        let host = match (
+            self.secure,
+            &cloudinary.secure_distribution,
+            &cloudinary.cname,
+        ) {
+            (true, Some(secure_distribution), _) => secure_distribution.clone(),
+            (false, _, Some(cname)) => cname.clone(),
+            _ if private_cdn => format!("{cloud_name}-{SHARED_CDN}"),
+            _ => SHARED_CDN.to_string(),
+        };
+        let scheme = if self.secure { "https" } else { "http" };
+
+        if private_cdn {
+            format!("{scheme}://{host}")
+        } else {
+            format!("{scheme}://{host}/{cloud_name}")
+        }
+    }
+}
+
+/// Whether the public_id already starts with a version, e.g. `v1234/folder/sample`.
+fn is_versioned(public_id: &str) -> bool {
+    public_id
+        .split('/')
+        .next()
+        .and_then(|segment| segment.strip_prefix('v'))
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn is_remote(public_id: &str) -> bool {
+    public_id.starts_with("http://") || public_id.starts_with("https://")
+}
+
+/// Percent-encodes the characters that cannot appear as is in a URL path.
+pub(crate) fn escape(public_id: &str) -> String {
+    public_id
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'.' | b'-' | b'/' | b':' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transformation::Transformation;
+    use crate::upload::{DeliveryType, ResourceTypes};
+    use crate::{Cloudinary, CloudinaryBuilder};
+
+    #[test]
+    fn delivery_url() {
+        let cloudinary = Cloudinary::new("demo", 123456789, "api_secret");
+        assert_eq!(
+            cloudinary.delivery_url("sample").build(),
+            "https://res.cloudinary.com/demo/image/upload/sample"
+        );
+        assert_eq!(
+            cloudinary
+                .delivery_url("folder/sample")
+                .set_format("jpg")
+                .build(),
+            "https://res.cloudinary.com/demo/image/upload/v1/folder/sample.jpg"
+        );
+        assert_eq!(
+            cloudinary
+                .delivery_url("folder/dog video")
+                .set_resource_type(ResourceTypes::Video)
+                .set_version(1312461204)
+                .set_transformation(Transformation::new().set_width(300))
+                .set_secure(false)
+                .build(),
+            "http://res.cloudinary.com/demo/video/upload/w_300/v1312461204/folder/dog%20video"
+        );
+        assert_eq!(
+            cloudinary
+                .delivery_url("sample")
+                .set_transformation("c_fill,w_300/e_grayscale")
+                .build(),
+            "https://res.cloudinary.com/demo/image/upload/c_fill,w_300/e_grayscale/sample"
+        );
+        assert_eq!(
+            cloudinary
+                .delivery_url("https://example.com/a.jpg")
+                .set_delivery_type(DeliveryType::Fetch)
+                .build(),
+            "https://res.cloudinary.com/demo/image/fetch/https://example.com/a.jpg"
+        );
+    }
+
+    #[test]
+    fn delivery_url_hosts() {
+        let cloudinary = CloudinaryBuilder::new("demo", 123456789, "api_secret")
+            .set_private_cdn(true)
+            .set_cname("assets.example.com")
+            .build();
+        assert_eq!(
+            cloudinary.delivery_url("sample").build(),
+            "https://demo-res.cloudinary.com/image/upload/sample"
+        );
+        assert_eq!(
+            cloudinary.delivery_url("sample").set_secure(false).build(),
+            "http://assets.example.com/image/upload/sample"
+        );
+
+        let cloudinary = CloudinaryBuilder::new("demo", 123456789, "api_secret")
+            .set_secure(false)
+            .build();
+        assert_eq!(
+            cloudinary.delivery_url("sample").build(),
+            "http://res.cloudinary.com/demo/image/upload/sample"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use core::fmt;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::error::Error;
+use std::io;
+
+use crate::response::{self, RateLimit};
+use crate::transport::TransportError;
+
+#[derive(Debug)]
+pub enum CloudinaryError {
+    /// Reading the uploaded file or a journal failed.
+    Io(io::Error),
+    /// The request could not be sent or its response not received.
+    Transport(TransportError),
+    /// Cloudinary answered with an unsuccessful status and no error message.
+    Http {
+        status: StatusCode,
+        headers: HeaderMap,
+        body: String,
+    },
+    /// The response or a journal could not be decoded.
+    Json(serde_json::Error),
+    /// The client configuration or the arguments of a call are invalid.
+    Configuration(String),
+    /// Cloudinary rejected the request.
+    Api {
+        status: StatusCode,
+        headers: HeaderMap,
+        message: String,
+    },
+}
+
+impl CloudinaryError {
+    /// HTTP status of the response, when one was received.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            CloudinaryError::Http { status, .. } | CloudinaryError::Api { status, .. } => {
+                Some(*status)
+            }
+            _ => None,
+        }
+    }
+
+    /// Headers of the response, when one was received.
+    pub fn headers(&self) -> Option<&HeaderMap> {
+        match self {
+            CloudinaryError::Http { headers, .. } | CloudinaryError::Api { headers, .. } => {
+                Some(headers)
+            }
+            _ => None,
+        }
+    }
+
+    /// Rate limit reported by the response, e.g. to wait before calling again after a 420 or 429.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        RateLimit::from_headers(self.headers()?)
+    }
+
+    /// Id Cloudinary assigned to the request, useful when contacting support.
+    pub fn request_id(&self) -> Option<&str> {
+        response::request_id(self.headers()?)
+    }
+
+    /// Error message returned by Cloudinary.
+    pub fn api_message(&self) -> Option<&str> {
+        match self {
+            CloudinaryError::Api { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CloudinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CloudinaryError::Io(err) => write!(f, "I/O error: {}", err),
+            CloudinaryError::Transport(err) => write!(f, "Transport error: {}", err),
+            CloudinaryError::Http { status, body, .. } => {
+                write!(f, "Unexpected HTTP status {}: {}", status, body)
+            }
+            CloudinaryError::Json(err) => write!(f, "Invalid JSON: {}", err),
+            CloudinaryError::Configuration(message) => {
+                write!(f, "Invalid configuration: {}", message)
+            }
+            CloudinaryError::Api {
+                status, message, ..
+            } => {
+                write!(f, "Cloudinary error {}: {}", status, message)
+            }
+        }
+    }
+}
+
+impl Error for CloudinaryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CloudinaryError::Io(err) => Some(err),
+            CloudinaryError::Transport(err) => Some(err),
+            CloudinaryError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CloudinaryError {
+    fn from(err: io::Error) -> Self {
+        CloudinaryError::Io(err)
+    }
+}
+
+impl From<TransportError> for CloudinaryError {
+    fn from(err: TransportError) -> Self {
+        CloudinaryError::Transport(err)
+    }
+}
+
+impl From<reqwest::Error> for CloudinaryError {
+    fn from(err: reqwest::Error) -> Self {
+        CloudinaryError::Transport(err.into())
+    }
+}
+
+impl From<serde_json::Error> for CloudinaryError {
+    fn from(err: serde_json::Error) -> Self {
+        CloudinaryError::Json(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::io;
+
+    use super::CloudinaryError;
+    use crate::transport::TransportError;
+
+    #[test]
+    fn keeps_source_chain() {
+        let io_error = CloudinaryError::from(io::Error::new(io::ErrorKind::NotFound, "no journal"));
+        assert_eq!(io_error.to_string(), "I/O error: no journal");
+        let source = io_error.source().unwrap().downcast_ref::<io::Error>();
+        assert_eq!(source.map(io::Error::kind), Some(io::ErrorKind::NotFound));
+
+        let reset = io::Error::new(io::ErrorKind::ConnectionReset, "reset");
+        let transport_error = CloudinaryError::from(TransportError::new(reset));
+        assert_eq!(transport_error.to_string(), "Transport error: reset");
+        let source = transport_error
+            .source()
+            .unwrap()
+            .downcast_ref::<TransportError>()
+            .unwrap()
+            .source()
+            .unwrap()
+            .downcast_ref::<io::Error>();
+        assert_eq!(
+            source.map(io::Error::kind),
+            Some(io::ErrorKind::ConnectionReset)
+        );
+
+        let json_error = CloudinaryError::from(serde_json::from_str::<u32>("{}").unwrap_err());
+        assert!(json_error.to_string().starts_with("Invalid JSON: "));
+        let source = json_error.source().unwrap();
+        assert!(source.downcast_ref::<serde_json::Error>().is_some());
+
+        let configuration_error = CloudinaryError::Configuration(String::from("Missing filename"));
+        assert_eq!(
+            configuration_error.to_string(),
+            "Invalid configuration: Missing filename"
+        );
+        assert!(configuration_error.source().is_none());
+
+        for error in [io_error, transport_error, json_error, configuration_error] {
+            assert_eq!(error.status(), None);
+            assert_eq!(error.api_message(), None);
+            assert!(error.headers().is_none());
+        }
+    }
+}
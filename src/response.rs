@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::ops::Deref;
+
+const HEADER_RATE_LIMIT_LIMIT: &str = "X-FeatureRateLimit-Limit";
+const HEADER_RATE_LIMIT_REMAINING: &str = "X-FeatureRateLimit-Remaining";
+const HEADER_RATE_LIMIT_RESET: &str = "X-FeatureRateLimit-Reset";
+const HEADER_REQUEST_ID: &str = "X-Request-Id";
+
+/// A decoded response, along with the HTTP details it was received with,
+/// as returned by the `*_with_response` calls. Dereferences to the decoded result.
+#[derive(Clone, Debug)]
+pub struct ApiResponse<T> {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// Raw body the result was decoded from.
+    pub body: String,
+    pub result: T,
+}
+
+/// Rate limit of the called feature, as reported by the Admin API.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Calls allowed per period.
+    pub limit: u64,
+    /// Calls left in the current period.
+    pub remaining: u64,
+    /// When the current period ends.
+    pub reset: Option<DateTime<Utc>>,
+}
+
+impl<T> ApiResponse<T> {
+    /// Rate limit reported by the response, if any.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        RateLimit::from_headers(&self.headers)
+    }
+
+    /// Id Cloudinary assigned to the request, useful when contacting support.
+    pub fn request_id(&self) -> Option<&str> {
+        request_id(&self.headers)
+    }
+
+    pub fn into_result(self) -> T {
+        self.result
+    }
+}
+
+impl RateLimit {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Some(RateLimit {
+            limit: header(headers, HEADER_RATE_LIMIT_LIMIT)?.parse().ok()?,
+            remaining: header(headers, HEADER_RATE_LIMIT_REMAINING)?.parse().ok()?,
+            reset: header(headers, HEADER_RATE_LIMIT_RESET)
+                .and_then(|reset| DateTime::parse_from_rfc2822(reset).ok())
+                .map(|reset| reset.with_timezone(&Utc)),
+        })
+    }
+}
+
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<&str> {
+    header(headers, HEADER_REQUEST_ID)
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok().map(str::trim)
+}
+
+impl<T> Deref for ApiResponse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use reqwest::header::HeaderMap;
+    use reqwest::StatusCode;
+
+    use super::{ApiResponse, RateLimit};
+
+    #[test]
+    fn rate_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-FeatureRateLimit-Limit", "500".parse().unwrap());
+        headers.insert("X-FeatureRateLimit-Remaining", "499".parse().unwrap());
+        headers.insert(
+            "X-FeatureRateLimit-Reset",
+            "Wed, 21 Jun 2023 10:00:00 GMT".parse().unwrap(),
+        );
+        headers.insert("X-Request-Id", "abc123".parse().unwrap());
+        let response = ApiResponse {
+            status: StatusCode::OK,
+            headers,
+            body: String::from("{}"),
+            result: (),
+        };
+
+        assert_eq!(
+            response.rate_limit(),
+            Some(RateLimit {
+                limit: 500,
+                remaining: 499,
+                reset: Some(Utc.with_ymd_and_hms(2023, 6, 21, 10, 0, 0).unwrap()),
+            })
+        );
+        assert_eq!(response.request_id(), Some("abc123"));
+    }
+}
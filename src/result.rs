@@ -76,24 +76,3 @@ pub struct RenameResponse {
 pub struct DeleteResponse {
     pub result: String,
 }
-
-#[derive(Clone, Deserialize, Debug)]
-#[serde(untagged)]
-pub enum CloudinaryUploadResult {
-    Succes(Box<UploadResponse>),
-    Error(Box<Error>),
-}
-
-#[derive(Clone, Deserialize, Debug)]
-#[serde(untagged)]
-pub enum CloudinaryRenameResult {
-    Succes(Box<RenameResponse>),
-    Error(Box<Error>),
-}
-
-#[derive(Clone, Deserialize, Debug)]
-#[serde(untagged)]
-pub enum CloudinaryDeleteResult {
-    Succes(Box<DeleteResponse>),
-    Error(Box<Error>),
-}
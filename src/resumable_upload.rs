@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tokio::fs::{self, File};
+use tokio::io::AsyncSeekExt;
+
+use crate::result::UploadResponse;
+use crate::upload::progress::ProgressCallback;
+use crate::upload::upload_source::{file_name, read_chunk};
+use crate::upload::UploadSource;
+use crate::upload::{ResourceTypes, UploadOptions, UploadProgress};
+use crate::upload_large::{check_chunk_size, new_unique_upload_id, Chunk};
+use crate::{parse_response, upload_params, Cloudinary, CloudinaryError};
+
+/// Bytes hashed from the start of the file to detect a replaced source.
+const FINGERPRINT_HEAD_SIZE: usize = 64 * 1024;
+
+/// A chunked upload whose progress is persisted to a journal file,
+/// so it can be resumed by another process after a failure.
+/// ```rust,no_run
+/// use cloudinary::{Cloudinary, DEFAULT_CHUNK_SIZE};
+/// use cloudinary::upload::UploadOptions;
+/// # async fn run() {
+/// let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+/// let options = UploadOptions::new();
+/// let mut upload = match cloudinary.open_resumable_upload("./video.journal").await {
+///     Ok(upload) => upload,
+///     Err(_) => cloudinary
+///         .start_resumable_upload("./video.mp4", &options, DEFAULT_CHUNK_SIZE, "./video.journal")
+///         .await
+///         .unwrap(),
+/// };
+/// let result = upload.resume().await;
+/// # }
+/// ```
+pub struct ResumableUpload {
+    cloudinary: Cloudinary,
+    journal_path: PathBuf,
+    journal: UploadJournal,
+    progress: Option<ProgressCallback>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UploadJournal {
+    unique_upload_id: String,
+    file_path: PathBuf,
+    fingerprint: SourceFingerprint,
+    resource_type: ResourceTypes,
+    content_type: String,
+    params: BTreeMap<String, String>,
+    chunk_size: usize,
+    /// Inclusive byte ranges acknowledged by Cloudinary.
+    completed_ranges: Vec<(u64, u64)>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+struct SourceFingerprint {
+    length: u64,
+    modified: Option<SystemTime>,
+    head_sha1: String,
+}
+
+impl Cloudinary {
+    /// Starts a resumable upload of a local file, overwriting any journal at `journal_path`.
+    /// Nothing is sent until [`ResumableUpload::resume`] is called.
+    /// `file_path` is saved as an absolute path, so the journal can be opened from any directory.
+    /// `chunk_size` is limited as for [`Cloudinary::upload_large`].
+    pub async fn start_resumable_upload(
+        &self,
+        file_path: impl Into<PathBuf>,
+        options: &UploadOptions<'_>,
+        chunk_size: usize,
+        journal_path: impl Into<PathBuf>,
+    ) -> Result<ResumableUpload, CloudinaryError> {
+        check_chunk_size(chunk_size)?;
+
+        let options = &options.with_defaults(&self.default_options);
+        let params = upload_params(options);
+        self.check_params(&params)?;
+        // Absolute, so the upload can be resumed from another working directory
+        let file_path = fs::canonicalize(file_path.into()).await?;
+        let resource_type = options.get_resource_type().unwrap_or(ResourceTypes::Image);
+        let content_type = UploadSource::path(&file_path)
+            .content_type(&resource_type, options)
+            .await?;
+
+        let upload = ResumableUpload {
+            cloudinary: self.clone(),
+            journal_path: journal_path.into(),
+            progress: options.get_progress().cloned(),
+            journal: UploadJournal {
+                unique_upload_id: new_unique_upload_id(),
+                fingerprint: SourceFingerprint::of(&file_path).await?,
+                file_path,
+                resource_type,
+                content_type,
+                params,
+                chunk_size,
+                completed_ranges: vec![],
+            },
+        };
+        upload.save_journal().await?;
+        Ok(upload)
+    }
+
+    /// Reopens a resumable upload from its journal.
+    /// Fails if the source file changed since the upload started.
+    pub async fn open_resumable_upload(
+        &self,
+        journal_path: impl Into<PathBuf>,
+    ) -> Result<ResumableUpload, CloudinaryError> {
+        let journal_path = journal_path.into();
+        let text = fs::read_to_string(&journal_path).await?;
+        let journal: UploadJournal = serde_json::from_str(&text)?;
+
+        if SourceFingerprint::of(&journal.file_path).await? != journal.fingerprint {
+            return Err(CloudinaryError::Configuration(String::from(
+                "Source file changed since the upload started.",
+            )));
+        }
+
+        Ok(ResumableUpload {
+            cloudinary: self.clone(),
+            journal_path,
+            journal,
+            progress: None,
+        })
+    }
+}
+
+impl ResumableUpload {
+    pub fn unique_upload_id(&self) -> &str {
+        &self.journal.unique_upload_id
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.journal.fingerprint.length
+    }
+
+    pub fn bytes_uploaded(&self) -> u64 {
+        self.journal
+            .completed_ranges
+            .iter()
+            .map(|(start, end)| end - start + 1)
+            .sum()
+    }
+
+    /// Calls `progress` after each chunk is acknowledged.
+    /// Bytes acknowledged before the upload was resumed are counted as sent.
+    pub fn set_progress(&mut self, progress: impl Fn(UploadProgress) + Send + Sync + 'static) {
+        self.progress = Some(ProgressCallback::new(progress));
+    }
+
+    /// Uploads the chunks not yet acknowledged, recording each one in the journal.
+    /// The journal is removed once the upload completes; on error it is kept
+    /// so the upload can be resumed again.
+    pub async fn resume(&mut self) -> Result<UploadResponse, CloudinaryError> {
+        let total = self.journal.fingerprint.length;
+        if total == 0 {
+            return Err(CloudinaryError::Configuration(String::from(
+                "Upload source is empty.",
+            )));
+        }
+
+        let mut file = File::open(&self.journal.file_path).await?;
+        let filename = file_name(&self.journal.file_path)?;
+
+        let chunk_size = self.journal.chunk_size as u64;
+        let mut start = 0;
+        loop {
+            let end = (start + chunk_size).min(total) - 1;
+            let is_last = end + 1 == total;
+
+            // The last chunk is always sent, as its response carries the upload result
+            if is_last || !self.journal.completed_ranges.contains(&(start, end)) {
+                file.seek(SeekFrom::Start(start)).await?;
+                let chunk = read_chunk(&mut file, (end - start + 1) as usize).await?;
+
+                let chunk = Chunk {
+                    data: chunk,
+                    filename: &filename,
+                    content_type: &self.journal.content_type,
+                    content_range: format!("bytes {start}-{end}/{total}"),
+                };
+                let response = self
+                    .cloudinary
+                    .upload_chunk(
+                        &chunk,
+                        &self.journal.resource_type,
+                        &self.journal.params,
+                        &self.journal.unique_upload_id,
+                    )
+                    .await?;
+
+                if is_last {
+                    let result = parse_response::<UploadResponse>(response)?.into_result();
+                    self.remove_journal().await?;
+                    if let Some(progress) = &self.progress {
+                        progress.report(total, Some(total));
+                    }
+                    return Ok(result);
+                }
+
+                self.journal.completed_ranges.push((start, end));
+                self.save_journal().await?;
+                if let Some(progress) = &self.progress {
+                    progress.report(self.bytes_uploaded(), Some(total));
+                }
+            }
+
+            start = end + 1;
+        }
+    }
+
+    /// Gives up on the upload and removes its journal.
+    /// Chunks already sent expire on Cloudinary's side.
+    pub async fn abandon(self) -> Result<(), CloudinaryError> {
+        self.remove_journal().await
+    }
+
+    /// Writes the journal atomically, so a crash never leaves it half written.
+    async fn save_journal(&self) -> Result<(), CloudinaryError> {
+        let text = serde_json::to_string(&self.journal)?;
+        // Next to the journal, without replacing its extension which could clash with another file
+        let mut temporary_path = self.journal_path.clone().into_os_string();
+        temporary_path.push(".tmp");
+        let temporary_path = PathBuf::from(temporary_path);
+        fs::write(&temporary_path, text).await?;
+        fs::rename(&temporary_path, &self.journal_path)
+            .await
+            .map_err(CloudinaryError::from)
+    }
+
+    async fn remove_journal(&self) -> Result<(), CloudinaryError> {
+        fs::remove_file(&self.journal_path)
+            .await
+            .map_err(CloudinaryError::from)
+    }
+}
+
+impl SourceFingerprint {
+    async fn of(file_path: &Path) -> Result<Self, CloudinaryError> {
+        let mut file = File::open(file_path).await?;
+        let metadata = file.metadata().await?;
+
+        let head = read_chunk(&mut file, FINGERPRINT_HEAD_SIZE).await?;
+
+        Ok(Self {
+            length: metadata.len(),
+            modified: metadata.modified().ok(),
+            head_sha1: format!("{:x}", Sha1::digest(&head)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::fs;
+
+    use crate::transport::fake::{cloudinary, temp_dir, FakeTransport};
+    use crate::upload::UploadOptions;
+    use crate::{Cloudinary, CloudinaryError};
+
+    #[tokio::test]
+    async fn journal_round_trip() {
+        let directory = temp_dir();
+        let file_path = directory.join("video.mp4");
+        let journal_path = directory.join("video.journal");
+        fs::write(&file_path, vec![0u8; 100]).await.unwrap();
+
+        let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+        let upload = cloudinary
+            .start_resumable_upload(
+                directory.join(".").join("video.mp4"),
+                &UploadOptions::new(),
+                10,
+                &journal_path,
+            )
+            .await
+            .unwrap();
+
+        let reopened = cloudinary
+            .open_resumable_upload(&journal_path)
+            .await
+            .unwrap();
+        assert_eq!(reopened.unique_upload_id(), upload.unique_upload_id());
+        assert_eq!(reopened.total_bytes(), 100);
+        assert_eq!(reopened.bytes_uploaded(), 0);
+        assert_eq!(
+            reopened.journal.file_path,
+            fs::canonicalize(&file_path).await.unwrap()
+        );
+
+        fs::write(&file_path, vec![1u8; 100]).await.unwrap();
+        assert!(cloudinary
+            .open_resumable_upload(&journal_path)
+            .await
+            .is_err());
+
+        reopened.abandon().await.unwrap();
+        assert!(!journal_path.exists());
+    }
+
+    #[tokio::test]
+    async fn resumes_unacknowledged_chunks() {
+        let directory = temp_dir();
+        let file_path = directory.join("video.mp4");
+        let journal_path = directory.join("video.journal");
+        let unrelated_path = directory.join("video.tmp");
+        let data: Vec<u8> = (0..100).collect();
+        fs::write(&file_path, &data).await.unwrap();
+        fs::write(&unrelated_path, "unrelated").await.unwrap();
+
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+        let mut upload = cloudinary
+            .start_resumable_upload(&file_path, &UploadOptions::new(), 30, &journal_path)
+            .await
+            .unwrap();
+
+        // Fails on the third chunk, after acknowledging two
+        transport.respond(200, "{}");
+        transport.respond(200, "{}");
+        transport.respond(500, "Internal Server Error");
+        let error = upload.resume().await.err();
+        assert!(matches!(error, Some(CloudinaryError::Http { .. })));
+
+        let mut upload = cloudinary
+            .open_resumable_upload(&journal_path)
+            .await
+            .unwrap();
+        assert_eq!(upload.bytes_uploaded(), 60);
+
+        // Fails on the last chunk, which is sent again even though the others are acknowledged
+        transport.respond(200, "{}");
+        transport.respond(500, "Internal Server Error");
+        assert!(upload.resume().await.is_err());
+        let mut upload = cloudinary
+            .open_resumable_upload(&journal_path)
+            .await
+            .unwrap();
+        assert_eq!(upload.bytes_uploaded(), 90);
+
+        let response = upload.resume().await.unwrap();
+        assert_eq!(response.public_id, "sample");
+        assert!(!journal_path.exists());
+        assert_eq!(
+            fs::read_to_string(&unrelated_path).await.unwrap(),
+            "unrelated"
+        );
+
+        let requests = transport.requests();
+        let ranges: Vec<_> = requests
+            .iter()
+            .map(|request| request.header("Content-Range").unwrap())
+            .collect();
+        assert_eq!(
+            ranges,
+            [
+                "bytes 0-29/100",
+                "bytes 30-59/100",
+                "bytes 60-89/100",
+                "bytes 60-89/100",
+                "bytes 90-99/100",
+                "bytes 90-99/100",
+            ]
+        );
+        assert!(
+            requests
+                .iter()
+                .all(|request| request.header("X-Unique-Upload-Id")
+                    == Some(upload.unique_upload_id()))
+        );
+        assert_eq!(requests[5].file.as_ref().unwrap().data, data[90..]);
+    }
+}
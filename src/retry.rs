@@ -0,0 +1,265 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use std::time::Duration;
+
+use crate::transport::{HttpResponse, TransportError};
+
+/// Kinds of transport errors that can be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryableError {
+    /// The request or the response timed out.
+    Timeout,
+    /// The connection could not be established.
+    Connect,
+    /// The request failed while being sent, e.g. a reset connection.
+    Request,
+}
+
+/// When and how failed calls are retried.
+///
+/// Delays grow exponentially from the initial backoff up to the max backoff,
+/// and are randomized when jitter is enabled. A `Retry-After` header sent by
+/// Cloudinary takes precedence, and a call is not retried when it asks to wait
+/// longer than the max backoff. Uploads from a stream are never retried.
+/// ```rust
+/// use cloudinary::{Cloudinary, RetryPolicy};
+/// use std::time::Duration;
+/// let cloudinary = Cloudinary::builder("cloud_name", 123456789, "api_secret")
+///     .set_retry_policy(RetryPolicy::new().set_max_attempts(5))
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<StatusCode>,
+    retryable_errors: Vec<RetryableError>,
+}
+
+impl RetryPolicy {
+    /// Up to 3 attempts, on rate limits (420, 429), server errors (500, 502, 503, 504),
+    /// timeouts and connection failures.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retryable_statuses: vec![
+                StatusCode::from_u16(420).unwrap(),
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retryable_errors: vec![RetryableError::Timeout, RetryableError::Connect],
+        }
+    }
+
+    /// Attempts of a call, including the first one.
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn set_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn set_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn set_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn set_retryable_statuses(mut self, retryable_statuses: Vec<StatusCode>) -> Self {
+        self.retryable_statuses = retryable_statuses;
+        self
+    }
+
+    pub fn set_retryable_errors(mut self, retryable_errors: Vec<RetryableError>) -> Self {
+        self.retryable_errors = retryable_errors;
+        self
+    }
+
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay before retrying a call answered with `response`, if it should be retried.
+    pub(crate) fn delay_after_response(
+        &self,
+        response: &HttpResponse,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.retryable_statuses.contains(&response.status) {
+            return None;
+        }
+
+        match retry_after(response) {
+            Some(delay) if delay > self.max_backoff => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Delay before retrying a call that failed with `err`, if it should be retried.
+    pub(crate) fn delay_after_error(&self, err: &TransportError, attempt: u32) -> Option<Duration> {
+        let kind = err.get_kind()?;
+        if attempt >= self.max_attempts || !self.retryable_errors.contains(&kind) {
+            return None;
+        }
+        Some(self.backoff(attempt))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_backoff);
+        if self.jitter {
+            // Between half and all of the backoff, so concurrent clients spread out
+            backoff.mul_f64(0.5 + rand::random::<f64>() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Delay asked by the `Retry-After` header, given in seconds or as an HTTP date.
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    let value = response.headers.get(RETRY_AFTER)?.to_str().ok()?;
+    if let Ok(seconds) = value.trim().parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now())
+        .to_std()
+        .ok()
+        .or(Some(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderMap;
+    use reqwest::StatusCode;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+    use crate::transport::fake::{self, FakeTransport};
+    use crate::transport::HttpResponse;
+    use crate::upload::{UploadOptions, UploadSource};
+    use crate::{Cloudinary, CloudinaryError};
+
+    fn cloudinary(transport: &FakeTransport, retry_policy: RetryPolicy) -> Cloudinary {
+        fake::builder(transport)
+            .set_retry_policy(retry_policy)
+            .build()
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::new()
+            .set_initial_backoff(Duration::from_secs(1))
+            .set_max_backoff(Duration::from_secs(5))
+            .set_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+
+        let policy = policy.set_jitter(true);
+        let backoff = policy.backoff(2);
+        assert!(backoff >= Duration::from_secs(1) && backoff <= Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn retries_with_fresh_signatures() {
+        let transport = FakeTransport::default();
+        // Timestamps are in seconds, so the retry waits for the next one
+        let retry_policy = RetryPolicy::new()
+            .set_initial_backoff(Duration::from_millis(1100))
+            .set_jitter(false);
+        let cloudinary = cloudinary(&transport, retry_policy);
+
+        transport.respond(503, "Service Unavailable");
+        let response = cloudinary.delete_image("sample").await.unwrap();
+        assert_eq!(response.result, "ok");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_ne!(
+            requests[0].field("timestamp"),
+            requests[1].field("timestamp")
+        );
+        assert_ne!(
+            requests[0].field("signature"),
+            requests[1].field("signature")
+        );
+    }
+
+    #[test]
+    fn retry_after() {
+        let policy = RetryPolicy::new().set_max_backoff(Duration::from_secs(10));
+        let mut response = HttpResponse {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            headers: HeaderMap::new(),
+            body: Default::default(),
+        };
+
+        response.headers.insert("Retry-After", "7".parse().unwrap());
+        assert_eq!(
+            policy.delay_after_response(&response, 1),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(policy.delay_after_response(&response, 3), None);
+        response
+            .headers
+            .insert("Retry-After", "60".parse().unwrap());
+        assert_eq!(policy.delay_after_response(&response, 1), None);
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_retry_after_exceeds_max_backoff() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport, RetryPolicy::new());
+
+        transport.respond_with_headers(429, &[("Retry-After", "120")], "Too Many Requests");
+        let error = cloudinary.delete_image("sample").await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn never_retries_streams() {
+        let transport = FakeTransport::default();
+        let retry_policy = RetryPolicy::new().set_initial_backoff(Duration::from_millis(1));
+        let cloudinary = cloudinary(&transport, retry_policy);
+
+        transport.respond(503, "Service Unavailable");
+        let source = UploadSource::stream(Cursor::new(vec![0u8; 10]), "data.bin", None);
+        let error = cloudinary
+            .upload(source, &UploadOptions::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, CloudinaryError::Http { .. }));
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
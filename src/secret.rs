@@ -0,0 +1,59 @@
+use core::fmt;
+use zeroize::Zeroize;
+
+/// A credential that is never printed, and is wiped from memory when dropped.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct Secret(String);
+
+impl Secret {
+    pub fn new(value: &str) -> Self {
+        Self(value.to_string())
+    }
+
+    /// The actual value, for signing. Never log it.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Secret;
+    use crate::Cloudinary;
+
+    #[test]
+    fn redacts() {
+        let secret = Secret::new("api_secret");
+        assert_eq!(format!("{secret:?} {secret}"), "*** ***");
+        assert_eq!(secret.expose(), "api_secret");
+
+        let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+        let debug = format!("{cloudinary:?}");
+        assert!(debug.contains("cloud_name") && debug.contains("123456789"));
+        assert!(!debug.contains("api_secret"));
+    }
+}
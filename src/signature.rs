@@ -0,0 +1,142 @@
+use itertools::Itertools;
+use sha1::digest::Output;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::CloudinaryError;
+
+/// Parameters sent with a request but never signed.
+const UNSIGNED_PARAMS: [&str; 5] = [
+    "file",
+    "api_key",
+    "resource_type",
+    "cloud_name",
+    "signature",
+];
+
+/// Signs request parameters the way Cloudinary verifies them:
+/// - `file`, `api_key`, `resource_type`, `cloud_name` and `signature` are excluded,
+///   as are parameters with an empty value;
+/// - the others are sorted by name and joined as `name=value` pairs separated by `&`;
+/// - the API secret is appended and the result hashed with `algorithm`.
+///
+/// Values must be sent exactly as signed: list values are joined with commas,
+/// like `tags=cat,dog`, and `timestamp` is in Unix seconds.
+/// ```rust
+/// use cloudinary::{sign_params, SignatureAlgorithm};
+/// use std::collections::BTreeMap;
+/// let params = BTreeMap::from([
+///     (String::from("eager"), String::from("w_400,h_300,c_pad|w_260,h_200,c_crop")),
+///     (String::from("public_id"), String::from("sample_image")),
+///     (String::from("timestamp"), String::from("1315060510")),
+/// ]);
+/// assert_eq!(
+///     sign_params(&params, "abcd", SignatureAlgorithm::Sha1),
+///     "bfd09f95f331f558cbd1320e67aa8d488770583e"
+/// );
+/// ```
+pub fn sign_params(
+    params: &BTreeMap<String, String>,
+    api_secret: &str,
+    algorithm: SignatureAlgorithm,
+) -> String {
+    let payload = params
+        .iter()
+        .filter(|(key, value)| !value.is_empty() && !UNSIGNED_PARAMS.contains(&key.as_str()))
+        .map(|(key, value)| format!("{key}={value}"))
+        .join("&");
+    algorithm.sign(&payload, api_secret)
+}
+
+/// Hash function of request signatures, which must match the setting of the Cloudinary account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+}
+
+impl SignatureAlgorithm {
+    /// Hex digest of `payload` followed by `api_secret`, as used by every Cloudinary signature.
+    pub fn sign(&self, payload: &str, api_secret: &str) -> String {
+        match self {
+            SignatureAlgorithm::Sha1 => hex_digest::<Sha1>(payload, api_secret),
+            SignatureAlgorithm::Sha256 => hex_digest::<Sha256>(payload, api_secret),
+        }
+    }
+}
+
+fn hex_digest<D: Digest>(payload: &str, api_secret: &str) -> String
+where
+    Output<D>: std::fmt::LowerHex,
+{
+    let mut hasher = D::new();
+    hasher.update(payload);
+    hasher.update(api_secret);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Parses the `signature_algorithm` setting, `sha1` or `sha256`.
+impl FromStr for SignatureAlgorithm {
+    type Err = CloudinaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha1" => Ok(SignatureAlgorithm::Sha1),
+            "sha256" => Ok(SignatureAlgorithm::Sha256),
+            _ => Err(CloudinaryError::Configuration(format!(
+                "Unsupported signature algorithm: {s}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{sign_params, SignatureAlgorithm};
+
+    const PAYLOAD: &str =
+        "eager=w_400,h_300,c_pad|w_260,h_200,c_crop&public_id=sample_image&timestamp=1315060510";
+
+    #[test]
+    fn sign() {
+        assert_eq!(
+            SignatureAlgorithm::Sha1.sign(PAYLOAD, "abcd"),
+            "bfd09f95f331f558cbd1320e67aa8d488770583e"
+        );
+        assert_eq!(
+            SignatureAlgorithm::Sha256.sign(PAYLOAD, "abcd"),
+            "cc927e1290f9e3ae4c1a741eda21a4630b4ce80f9ce0bc0296337d25cf40f91e"
+        );
+    }
+
+    #[test]
+    fn signs_only_signed_params() {
+        let params = BTreeMap::from([
+            ("timestamp", "1315060510"),
+            ("public_id", "sample_image"),
+            ("eager", "w_400,h_300,c_pad|w_260,h_200,c_crop"),
+            ("file", "https://example.com/sample.jpg"),
+            ("api_key", "123456789"),
+            ("resource_type", "image"),
+            ("cloud_name", "demo"),
+            ("folder", ""),
+        ])
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+        assert_eq!(
+            sign_params(&params, "abcd", SignatureAlgorithm::Sha1),
+            "bfd09f95f331f558cbd1320e67aa8d488770583e"
+        );
+        assert_eq!(
+            sign_params(&params, "abcd", SignatureAlgorithm::Sha256),
+            "cc927e1290f9e3ae4c1a741eda21a4630b4ce80f9ce0bc0296337d25cf40f91e"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::upload::UploadOptions;
+use crate::{Cloudinary, CloudinaryError, UPLOAD_OPTION_TIMESTAMP};
+
+/// Signed fields of an upload sent by another client, e.g. a browser or the Upload Widget.
+/// Serializes to a flat object of the form fields to post along with the file.
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedUpload {
+    pub timestamp: i64,
+    pub signature: String,
+    pub api_key: String,
+    /// Every option, as serialized by [`UploadOptions::get_map`].
+    #[serde(flatten)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl Cloudinary {
+    /// Signs the options of an upload, for a client that must not hold the API secret.
+    /// The upload must be sent before the signature expires, one hour after it is created.
+    /// ```rust
+    /// use cloudinary::Cloudinary;
+    /// use cloudinary::upload::UploadOptions;
+    /// let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+    /// let options = UploadOptions::new().set_public_id(String::from("sample"));
+    /// let signed = cloudinary.sign_upload(&options).unwrap();
+    /// let fields = serde_json::to_string(&signed).unwrap();
+    /// ```
+    pub fn sign_upload(
+        &self,
+        options: &UploadOptions<'_>,
+    ) -> Result<SignedUpload, CloudinaryError> {
+        self.check_signed()?;
+        let options = options.with_defaults(&self.default_options);
+        let mut params = options.get_map();
+        let timestamp = Utc::now().timestamp();
+        let signature = self.build_signature(&mut params, timestamp);
+        params.remove(UPLOAD_OPTION_TIMESTAMP);
+
+        Ok(SignedUpload {
+            timestamp,
+            signature,
+            api_key: self.api_key.to_string(),
+            params,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::upload::UploadOptions;
+    use crate::{sign_params, Cloudinary, SignatureAlgorithm};
+
+    #[test]
+    fn sign_upload() {
+        let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+        let options = UploadOptions::new().set_public_id(String::from("sample"));
+        let signed = cloudinary.sign_upload(&options).unwrap();
+
+        let mut params = signed.params.clone();
+        params.insert(String::from("timestamp"), signed.timestamp.to_string());
+        assert_eq!(
+            signed.signature,
+            sign_params(&params, "api_secret", SignatureAlgorithm::Sha1)
+        );
+
+        let fields = serde_json::to_value(&signed).unwrap();
+        assert_eq!(fields["public_id"], "sample");
+        assert_eq!(fields["api_key"], "123456789");
+        assert_eq!(fields["timestamp"], signed.timestamp);
+    }
+}
@@ -0,0 +1,224 @@
+use std::future::Future;
+
+use crate::transport::{HttpRequest, HttpResponse, TransportError};
+
+/// Span of an API call, opened when the `tracing` feature is enabled.
+/// Only records values that are safe to log: never the secret nor the signature.
+pub(crate) struct RequestTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+    /// Bytes of the file read by the transport during the last attempt.
+    #[cfg(feature = "tracing")]
+    bytes_sent: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+
+#[cfg(feature = "tracing")]
+impl RequestTrace {
+    pub fn new(cloud_name: &str) -> Self {
+        use tracing::field::Empty;
+
+        Self {
+            span: tracing::info_span!(
+                "cloudinary.request",
+                cloud_name,
+                endpoint = Empty,
+                resource_type = Empty,
+                public_id = Empty,
+                bytes_sent = Empty,
+                status = Empty,
+                latency_ms = Empty,
+                retries = Empty,
+            ),
+            start: std::time::Instant::now(),
+            bytes_sent: Default::default(),
+        }
+    }
+
+    pub async fn instrument<F: Future>(&self, future: F) -> F::Output {
+        tracing::Instrument::instrument(future, self.span.clone()).await
+    }
+
+    /// Records the request, and counts the bytes of its file as the transport reads them.
+    pub fn record_request(&self, request: &mut HttpRequest) {
+        use crate::upload::progress::{ProgressCallback, ProgressReader};
+        use std::sync::atomic::Ordering;
+
+        self.span.record("endpoint", request.url.as_str());
+        // API urls end with `{resource_type}/{action}`
+        if let Some(resource_type) = request.url.rsplit('/').nth(1) {
+            self.span.record("resource_type", resource_type);
+        }
+        let public_id = request
+            .fields
+            .iter()
+            .find(|(name, _)| name == "public_id" || name == "from_public_id");
+        if let Some((_, public_id)) = public_id {
+            self.span.record("public_id", public_id.as_str());
+        }
+
+        self.bytes_sent.store(0, Ordering::Relaxed);
+        if let Some(file) = &mut request.file {
+            let bytes_sent = self.bytes_sent.clone();
+            let progress = ProgressCallback::new(move |progress| {
+                bytes_sent.store(progress.bytes_sent, Ordering::Relaxed)
+            });
+            let reader = std::mem::replace(&mut file.reader, Box::pin(tokio::io::empty()));
+            file.reader = Box::pin(ProgressReader::new(reader, progress, file.length));
+        }
+    }
+
+    pub fn record_response(&self, result: &Result<HttpResponse, TransportError>, attempt: u32) {
+        if let Ok(response) = result {
+            self.span.record("status", response.status.as_u16());
+        }
+        self.span
+            .record("latency_ms", self.start.elapsed().as_millis() as u64);
+        self.span.record("retries", attempt - 1);
+        self.span.record(
+            "bytes_sent",
+            self.bytes_sent.load(std::sync::atomic::Ordering::Relaxed),
+        );
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl RequestTrace {
+    pub fn new(_cloud_name: &str) -> Self {
+        Self {}
+    }
+
+    pub async fn instrument<F: Future>(&self, future: F) -> F::Output {
+        future.await
+    }
+
+    pub fn record_request(&self, _request: &mut HttpRequest) {}
+
+    pub fn record_response(&self, _result: &Result<HttpResponse, TransportError>, _attempt: u32) {}
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use core::fmt;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::transport::fake::{cloudinary, FakeTransport};
+    use crate::upload::{UploadOptions, UploadSource};
+
+    /// Records the fields of every span and event.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl Visit for Capture {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            let field = (field.name().to_string(), value.to_string());
+            self.0.lock().unwrap().push(field);
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            let field = (field.name().to_string(), format!("{value:?}"));
+            self.0.lock().unwrap().push(field);
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            span.record(&mut self.clone());
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, values: &Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[tokio::test]
+    async fn records_requests_without_secrets() {
+        let capture = Capture::default();
+        let _guard = tracing::subscriber::set_default(capture.clone());
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+
+        let options = UploadOptions::new().set_public_id(String::from("sample"));
+        cloudinary
+            .upload(UploadSource::bytes(vec![0u8; 10], "data.bin"), &options)
+            .await
+            .unwrap();
+
+        let fields = capture.0.lock().unwrap();
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(field("cloud_name"), Some("cloud_name"));
+        assert_eq!(
+            field("endpoint"),
+            Some("https://api.cloudinary.com/v1_1/cloud_name/image/upload")
+        );
+        assert_eq!(field("resource_type"), Some("image"));
+        assert_eq!(field("public_id"), Some("sample"));
+        assert_eq!(field("bytes_sent"), Some("10"));
+        assert_eq!(field("status"), Some("200"));
+        assert_eq!(field("retries"), Some("0"));
+        assert!(field("latency_ms").is_some());
+
+        let requests = transport.requests();
+        let signature = requests[0].field("signature").unwrap();
+        assert!(fields.iter().all(|(key, value)| {
+            !key.contains("signature")
+                && !key.contains("secret")
+                && !value.contains(signature)
+                && !value.contains("api_secret")
+        }));
+    }
+
+    #[tokio::test]
+    async fn records_bytes_read() {
+        let capture = Capture::default();
+        let _guard = tracing::subscriber::set_default(capture.clone());
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+
+        let sources = [
+            UploadSource::stream(Cursor::new(vec![0u8; 10]), "data.bin", None),
+            // Ends before its declared length
+            UploadSource::stream(Cursor::new(vec![0u8; 10]), "data.bin", Some(20)),
+            UploadSource::url("https://example.com/sample.jpg"),
+        ];
+        for source in sources {
+            cloudinary
+                .upload(source, &UploadOptions::new())
+                .await
+                .unwrap();
+        }
+
+        let fields = capture.0.lock().unwrap();
+        let bytes_sent: Vec<_> = fields
+            .iter()
+            .filter(|(key, _)| key == "bytes_sent")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(bytes_sent, ["10", "10", "0"]);
+    }
+}
@@ -0,0 +1,39 @@
+use core::fmt;
+
+/// How the asset is resized to the requested dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CropMode {
+    Scale,
+    Fit,
+    Limit,
+    Mfit,
+    Fill,
+    Lfill,
+    Pad,
+    Lpad,
+    Mpad,
+    FillPad,
+    Crop,
+    Thumb,
+    Auto,
+}
+
+impl fmt::Display for CropMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CropMode::Scale => write!(f, "scale"),
+            CropMode::Fit => write!(f, "fit"),
+            CropMode::Limit => write!(f, "limit"),
+            CropMode::Mfit => write!(f, "mfit"),
+            CropMode::Fill => write!(f, "fill"),
+            CropMode::Lfill => write!(f, "lfill"),
+            CropMode::Pad => write!(f, "pad"),
+            CropMode::Lpad => write!(f, "lpad"),
+            CropMode::Mpad => write!(f, "mpad"),
+            CropMode::FillPad => write!(f, "fill_pad"),
+            CropMode::Crop => write!(f, "crop"),
+            CropMode::Thumb => write!(f, "thumb"),
+            CropMode::Auto => write!(f, "auto"),
+        }
+    }
+}
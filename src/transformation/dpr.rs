@@ -0,0 +1,19 @@
+use core::fmt;
+
+/// Device pixel ratio the dimensions are multiplied by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dpr {
+    Auto,
+    Value(f32),
+}
+
+impl fmt::Display for Dpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dpr::Auto => write!(f, "auto"),
+            // Whole ratios keep one decimal, like `dpr_2.0`, others all of theirs
+            Dpr::Value(value) if value.fract() == 0.0 => write!(f, "{:.1}", value),
+            Dpr::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
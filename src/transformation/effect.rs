@@ -0,0 +1,40 @@
+use core::fmt;
+
+/// An effect or filter. The optional value is the strength, with Cloudinary's default when unset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Grayscale,
+    Sepia(Option<u32>),
+    Blur(Option<u32>),
+    BlurFaces(Option<u32>),
+    Pixelate(Option<u32>),
+    Sharpen(Option<u32>),
+    /// From -99 to 100.
+    Brightness(i32),
+    /// From -100 to 100.
+    Contrast(i32),
+    /// From -100 to 100.
+    Saturation(i32),
+    BackgroundRemoval,
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (name, value) = match self {
+            Effect::Grayscale => ("grayscale", None),
+            Effect::Sepia(value) => ("sepia", value.map(|v| v.to_string())),
+            Effect::Blur(value) => ("blur", value.map(|v| v.to_string())),
+            Effect::BlurFaces(value) => ("blur_faces", value.map(|v| v.to_string())),
+            Effect::Pixelate(value) => ("pixelate", value.map(|v| v.to_string())),
+            Effect::Sharpen(value) => ("sharpen", value.map(|v| v.to_string())),
+            Effect::Brightness(value) => ("brightness", Some(value.to_string())),
+            Effect::Contrast(value) => ("contrast", Some(value.to_string())),
+            Effect::Saturation(value) => ("saturation", Some(value.to_string())),
+            Effect::BackgroundRemoval => ("background_removal", None),
+        };
+        match value {
+            Some(value) => write!(f, "{}:{}", name, value),
+            None => write!(f, "{}", name),
+        }
+    }
+}
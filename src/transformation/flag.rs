@@ -0,0 +1,32 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    Animated,
+    Attachment,
+    Awebp,
+    KeepIptc,
+    LayerApply,
+    Lossy,
+    PreserveTransparency,
+    Progressive,
+    Relative,
+    StripProfile,
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Flag::Animated => write!(f, "animated"),
+            Flag::Attachment => write!(f, "attachment"),
+            Flag::Awebp => write!(f, "awebp"),
+            Flag::KeepIptc => write!(f, "keep_iptc"),
+            Flag::LayerApply => write!(f, "layer_apply"),
+            Flag::Lossy => write!(f, "lossy"),
+            Flag::PreserveTransparency => write!(f, "preserve_transparency"),
+            Flag::Progressive => write!(f, "progressive"),
+            Flag::Relative => write!(f, "relative"),
+            Flag::StripProfile => write!(f, "strip_profile"),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use core::fmt;
+
+/// Part of the asset kept when cropping, or where an overlay is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gravity {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    Center,
+    Auto,
+    Face,
+    Faces,
+}
+
+impl fmt::Display for Gravity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Gravity::North => write!(f, "north"),
+            Gravity::NorthEast => write!(f, "north_east"),
+            Gravity::East => write!(f, "east"),
+            Gravity::SouthEast => write!(f, "south_east"),
+            Gravity::South => write!(f, "south"),
+            Gravity::SouthWest => write!(f, "south_west"),
+            Gravity::West => write!(f, "west"),
+            Gravity::NorthWest => write!(f, "north_west"),
+            Gravity::Center => write!(f, "center"),
+            Gravity::Auto => write!(f, "auto"),
+            Gravity::Face => write!(f, "face"),
+            Gravity::Faces => write!(f, "faces"),
+        }
+    }
+}
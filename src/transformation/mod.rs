@@ -0,0 +1,208 @@
+mod crop_mode;
+mod dpr;
+mod effect;
+mod flag;
+mod gravity;
+mod overlay;
+mod quality;
+mod radius;
+
+use core::fmt;
+use std::collections::BTreeMap;
+
+pub use self::{
+    crop_mode::CropMode, dpr::Dpr, effect::Effect, flag::Flag, gravity::Gravity, overlay::Overlay,
+    quality::Quality, radius::Radius,
+};
+
+/// A transformation, serialized to Cloudinary's syntax such as `c_fill,g_face,h_200,w_300`.
+///
+/// [`Transformation::chain`] applies the next parameters to the result of the previous ones.
+/// ```rust
+/// use cloudinary::transformation::{CropMode, Effect, Gravity, Transformation};
+/// let transformation = Transformation::new()
+///     .set_crop(CropMode::Fill)
+///     .set_gravity(Gravity::Face)
+///     .set_width(300)
+///     .set_height(200)
+///     .chain()
+///     .set_effect(Effect::Grayscale);
+/// assert_eq!(transformation.to_string(), "c_fill,g_face,h_200,w_300/e_grayscale");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transformation {
+    /// Serialized components applied before the current one.
+    chain: Vec<String>,
+    /// Parameters of the current component, sorted by key like the official SDKs do.
+    params: BTreeMap<&'static str, String>,
+}
+
+impl Transformation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A transformation already in Cloudinary's syntax, for parameters not modeled here.
+    pub fn raw(transformation: &str) -> Self {
+        Self {
+            chain: vec![transformation.to_string()],
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Starts a new component, applied to the result of the previous ones.
+    pub fn chain(mut self) -> Self {
+        if !self.params.is_empty() {
+            self.chain.push(component(&self.params));
+            self.params.clear();
+        }
+        self
+    }
+
+    pub fn set_crop(self, crop: CropMode) -> Self {
+        self.set("c", crop)
+    }
+
+    pub fn set_gravity(self, gravity: Gravity) -> Self {
+        self.set("g", gravity)
+    }
+
+    /// Width in pixels.
+    pub fn set_width(self, width: u32) -> Self {
+        self.set("w", width)
+    }
+
+    /// Height in pixels.
+    pub fn set_height(self, height: u32) -> Self {
+        self.set("h", height)
+    }
+
+    /// Aspect ratio, e.g. `16:9`.
+    pub fn set_aspect_ratio(self, width: u32, height: u32) -> Self {
+        self.set("ar", format!("{width}:{height}"))
+    }
+
+    pub fn set_quality(self, quality: Quality) -> Self {
+        self.set("q", quality)
+    }
+
+    /// Format the asset is converted to, e.g. `auto`, `webp` or `png`.
+    pub fn set_format(self, format: &str) -> Self {
+        self.set("f", format)
+    }
+
+    pub fn set_dpr(self, dpr: Dpr) -> Self {
+        self.set("dpr", dpr)
+    }
+
+    pub fn set_radius(self, radius: Radius) -> Self {
+        self.set("r", radius)
+    }
+
+    /// Applies one effect. Chain transformations to apply several.
+    pub fn set_effect(self, effect: Effect) -> Self {
+        self.set("e", effect)
+    }
+
+    pub fn set_overlay(self, overlay: Overlay) -> Self {
+        self.set("l", overlay)
+    }
+
+    pub fn add_flag(mut self, flag: Flag) -> Self {
+        self.params
+            .entry("fl")
+            .and_modify(|flags| *flags = format!("{flags}.{flag}"))
+            .or_insert_with(|| flag.to_string());
+        self
+    }
+
+    fn set(mut self, key: &'static str, value: impl fmt::Display) -> Self {
+        self.params.insert(key, value.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Transformation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let current = (!self.params.is_empty()).then(|| component(&self.params));
+        let components: Vec<&str> = self
+            .chain
+            .iter()
+            .map(String::as_str)
+            .chain(current.as_deref())
+            .collect();
+        write!(f, "{}", components.join("/"))
+    }
+}
+
+/// A transformation already in Cloudinary's syntax, see [`Transformation::raw`].
+impl From<&str> for Transformation {
+    fn from(transformation: &str) -> Self {
+        Transformation::raw(transformation)
+    }
+}
+
+impl From<String> for Transformation {
+    fn from(transformation: String) -> Self {
+        Transformation::raw(&transformation)
+    }
+}
+
+fn component(params: &BTreeMap<&'static str, String>) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{key}_{value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dpr, Flag, Gravity, Overlay, Quality, Radius, Transformation};
+
+    #[test]
+    fn serialize() {
+        let transformation = Transformation::raw("a_90")
+            .chain()
+            .set_overlay(Overlay::Text {
+                font_family: String::from("Arial"),
+                font_size: 40,
+                text: String::from("Hello, world/100%"),
+            })
+            .set_gravity(Gravity::South)
+            .chain()
+            .add_flag(Flag::LayerApply)
+            .chain()
+            .set_aspect_ratio(16, 9)
+            .set_quality(Quality::AutoGood)
+            .set_format("auto")
+            .set_dpr(Dpr::Value(2.0))
+            .set_radius(Radius::Max)
+            .add_flag(Flag::Progressive)
+            .add_flag(Flag::StripProfile);
+
+        assert_eq!(
+            transformation.to_string(),
+            "a_90/g_south,l_text:Arial_40:Hello%252C%20world%252F100%2525/fl_layer_apply/ar_16:9,dpr_2.0,f_auto,fl_progressive.strip_profile,q_auto:good,r_max"
+        );
+        assert_eq!(Transformation::new().chain().to_string(), "");
+        assert_eq!(
+            Transformation::new()
+                .set_overlay(Overlay::Image(String::from("logos/my logo")))
+                .to_string(),
+            "l_logos:my%20logo"
+        );
+    }
+
+    #[test]
+    fn dpr() {
+        let dpr = |value| Transformation::new().set_dpr(Dpr::Value(value)).to_string();
+        assert_eq!(dpr(1.25), "dpr_1.25");
+        assert_eq!(dpr(1.75), "dpr_1.75");
+        assert_eq!(dpr(3.0), "dpr_3.0");
+        assert_eq!(
+            Transformation::new().set_dpr(Dpr::Auto).to_string(),
+            "dpr_auto"
+        );
+    }
+}
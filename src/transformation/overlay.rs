@@ -0,0 +1,45 @@
+use core::fmt;
+
+use crate::delivery_url::escape;
+
+/// A layer placed over the asset, positioned with the gravity of the same transformation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Overlay {
+    /// Public id of an uploaded image.
+    Image(String),
+    Text {
+        font_family: String,
+        font_size: u32,
+        text: String,
+    },
+}
+
+impl fmt::Display for Overlay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // Folders are separated with colons in layer ids
+            Overlay::Image(public_id) => write!(f, "{}", escape(&public_id.replace('/', ":"))),
+            Overlay::Text {
+                font_family,
+                font_size,
+                text,
+            } => write!(
+                f,
+                "text:{}_{}:{}",
+                font_family,
+                font_size,
+                escape_text(text)
+            ),
+        }
+    }
+}
+
+/// Escapes the text twice, like the official SDKs: Cloudinary decodes the URL path once
+/// before parsing the transformation, and commas and slashes must still be escaped then.
+fn escape_text(text: &str) -> String {
+    let text = text
+        .replace('%', "%25")
+        .replace(',', "%2C")
+        .replace('/', "%2F");
+    escape(&text)
+}
@@ -0,0 +1,25 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    Auto,
+    AutoBest,
+    AutoGood,
+    AutoEco,
+    AutoLow,
+    /// From 1 to 100.
+    Value(u8),
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Quality::Auto => write!(f, "auto"),
+            Quality::AutoBest => write!(f, "auto:best"),
+            Quality::AutoGood => write!(f, "auto:good"),
+            Quality::AutoEco => write!(f, "auto:eco"),
+            Quality::AutoLow => write!(f, "auto:low"),
+            Quality::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
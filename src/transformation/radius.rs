@@ -0,0 +1,18 @@
+use core::fmt;
+
+/// Rounding of the corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radius {
+    /// A circle or an ellipse.
+    Max,
+    Pixels(u32),
+}
+
+impl fmt::Display for Radius {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Radius::Max => write!(f, "max"),
+            Radius::Pixels(pixels) => write!(f, "{}", pixels),
+        }
+    }
+}
@@ -0,0 +1,415 @@
+use bytes::Bytes;
+use core::fmt;
+use reqwest::header::HeaderMap;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Body, Client, Method, StatusCode};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio_util::codec::{BytesCodec, FramedRead};
+
+use crate::RetryableError;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Sends signed requests to Cloudinary.
+///
+/// [`ReqwestTransport`] is used by default. Implement it to use another HTTP client,
+/// or to answer calls from memory in tests.
+/// ```rust
+/// use cloudinary::Cloudinary;
+/// use cloudinary::transport::{BoxFuture, HttpRequest, HttpResponse, Transport, TransportError};
+/// use reqwest::StatusCode;
+///
+/// struct Fake;
+///
+/// impl Transport for Fake {
+///     fn send(&self, _request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+///         Box::pin(async move {
+///             Ok(HttpResponse {
+///                 status: StatusCode::OK,
+///                 headers: Default::default(),
+///                 body: r#"{"result":"ok"}"#.into(),
+///             })
+///         })
+///     }
+/// }
+///
+/// let cloudinary = Cloudinary::builder("cloud_name", 123456789, "api_secret")
+///     .set_transport(Fake)
+///     .build();
+/// ```
+pub trait Transport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>>;
+}
+
+/// A signed multipart request.
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub timeout: Option<Duration>,
+    /// Text fields of the form, including the signature.
+    pub fields: Vec<(String, String)>,
+    /// File sent as the `file` field of the form.
+    pub file: Option<FilePart>,
+}
+
+/// A file streamed in a multipart form.
+pub struct FilePart {
+    pub reader: Pin<Box<dyn AsyncRead + Send>>,
+    pub filename: String,
+    pub content_type: String,
+    /// Length of the file, when known.
+    pub length: Option<u64>,
+}
+
+impl fmt::Debug for FilePart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FilePart")
+            .field("filename", &self.filename)
+            .field("content_type", &self.content_type)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+/// The request could not be sent or its response not received.
+#[derive(Debug)]
+pub struct TransportError {
+    kind: Option<RetryableError>,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl TransportError {
+    pub fn new(source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            kind: None,
+            source: source.into(),
+        }
+    }
+
+    /// Classifies the failure, so the retry policy can decide to retry it.
+    pub fn set_kind(mut self, kind: RetryableError) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn get_kind(&self) -> Option<RetryableError> {
+        self.kind
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(err: reqwest::Error) -> Self {
+        let kind = if err.is_timeout() {
+            Some(RetryableError::Timeout)
+        } else if err.is_connect() {
+            Some(RetryableError::Connect)
+        } else if err.is_request() {
+            Some(RetryableError::Request)
+        } else {
+            None
+        };
+        Self {
+            kind,
+            source: Box::new(err),
+        }
+    }
+}
+
+/// Sends requests with a [`reqwest::Client`].
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        Box::pin(async move {
+            let mut form = Form::new();
+            for (name, value) in request.fields {
+                form = form.text(name, value);
+            }
+            if let Some(file) = request.file {
+                form = form.part("file", file_part(file)?);
+            }
+
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers)
+                .multipart(form);
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            let response = builder.send().await?;
+            Ok(HttpResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.bytes().await?,
+            })
+        })
+    }
+}
+
+/// Streams the file, sending its length when known.
+fn file_part(file: FilePart) -> Result<Part, TransportError> {
+    let stream = FramedRead::new(SyncReader(Mutex::new(file.reader)), BytesCodec::new());
+    let body = Body::wrap_stream(stream);
+    let part = match file.length {
+        Some(length) => Part::stream_with_length(body, length),
+        None => Part::stream(body),
+    };
+    Ok(part.file_name(file.filename).mime_str(&file.content_type)?)
+}
+
+/// Makes a `Send` reader `Sync`, as required by [`Body::wrap_stream`].
+/// The reader is only ever accessed through `&mut`, so the lock is never contended.
+struct SyncReader(Mutex<Pin<Box<dyn AsyncRead + Send>>>);
+
+impl AsyncRead for SyncReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.get_mut()
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+            .poll_read(cx, buf)
+    }
+}
+
+/// A transport answering from memory, shared by the tests of every module.
+#[cfg(test)]
+pub(crate) mod fake {
+    use reqwest::header::HeaderMap;
+    use reqwest::StatusCode;
+    use std::collections::VecDeque;
+    use std::ops::Deref;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex, MutexGuard};
+    use tokio::io::AsyncReadExt;
+
+    use super::{BoxFuture, HttpRequest, HttpResponse, Transport, TransportError};
+    use crate::{Cloudinary, CloudinaryBuilder};
+
+    /// Decodes as an upload, a rename and a delete response alike.
+    const RESPONSE: &str = r#"{
+        "result": "ok",
+        "asset_id": "3515c6000a548515f1134043f9785c2f",
+        "public_id": "sample",
+        "version": 1312461204,
+        "version_id": "7d2cc533bee9ff39f7da7414b61fce7e",
+        "signature": "abcdefghijklmnopqrstuvwxyz12345",
+        "width": 864,
+        "height": 576,
+        "format": "jpg",
+        "resource_type": "image",
+        "created_at": "2017-08-11T12:24:32Z",
+        "tags": [],
+        "bytes": 120253,
+        "type": "upload",
+        "etag": "5297de5d0abe6e8aaef2fd7ba5ab2e6d",
+        "placeholder": false,
+        "url": "http://res.cloudinary.com/demo/image/upload/v1312461204/sample.jpg",
+        "secure_url": "https://res.cloudinary.com/demo/image/upload/v1312461204/sample.jpg",
+        "original_filename": "sample",
+        "folder": "",
+        "api_key": "123456789"
+    }"#;
+
+    /// Records the requests it is sent and answers them with the queued responses,
+    /// or with a successful one once the queue is empty.
+    #[derive(Clone, Default)]
+    pub struct FakeTransport {
+        requests: Arc<Mutex<Vec<SentRequest>>>,
+        responses: Arc<Mutex<VecDeque<HttpResponse>>>,
+    }
+
+    /// A request received by [`FakeTransport`], with its file read in full.
+    #[derive(Debug)]
+    pub struct SentRequest {
+        pub url: String,
+        pub headers: HeaderMap,
+        pub fields: Vec<(String, String)>,
+        pub file: Option<SentFile>,
+    }
+
+    #[derive(Debug)]
+    pub struct SentFile {
+        pub filename: String,
+        pub content_type: String,
+        pub length: Option<u64>,
+        pub data: Vec<u8>,
+    }
+
+    impl FakeTransport {
+        /// Queues the response to the next request.
+        pub fn respond(&self, status: u16, body: &str) {
+            self.respond_with_headers(status, &[], body);
+        }
+
+        pub fn respond_with_headers(
+            &self,
+            status: u16,
+            headers: &[(&'static str, &str)],
+            body: &str,
+        ) {
+            let mut header_map = HeaderMap::new();
+            for (name, value) in headers {
+                header_map.insert(*name, value.parse().unwrap());
+            }
+            self.responses.lock().unwrap().push_back(HttpResponse {
+                status: StatusCode::from_u16(status).unwrap(),
+                headers: header_map,
+                body: body.to_string().into(),
+            });
+        }
+
+        pub fn requests(&self) -> MutexGuard<'_, Vec<SentRequest>> {
+            self.requests.lock().unwrap()
+        }
+    }
+
+    impl Transport for FakeTransport {
+        fn send(
+            &self,
+            request: HttpRequest,
+        ) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+            Box::pin(async move {
+                let file = match request.file {
+                    Some(mut file) => {
+                        let mut data = vec![];
+                        file.reader
+                            .read_to_end(&mut data)
+                            .await
+                            .map_err(TransportError::new)?;
+                        Some(SentFile {
+                            filename: file.filename,
+                            content_type: file.content_type,
+                            length: file.length,
+                            data,
+                        })
+                    }
+                    None => None,
+                };
+                self.requests.lock().unwrap().push(SentRequest {
+                    url: request.url,
+                    headers: request.headers,
+                    fields: request.fields,
+                    file,
+                });
+
+                let response = self.responses.lock().unwrap().pop_front();
+                Ok(response.unwrap_or_else(|| HttpResponse {
+                    status: StatusCode::OK,
+                    headers: HeaderMap::new(),
+                    body: RESPONSE.into(),
+                }))
+            })
+        }
+    }
+
+    /// A signed client sending its requests to `transport`.
+    pub fn cloudinary(transport: &FakeTransport) -> Cloudinary {
+        builder(transport).build()
+    }
+
+    pub fn builder(transport: &FakeTransport) -> CloudinaryBuilder {
+        Cloudinary::builder("cloud_name", 123456789, "api_secret").set_transport(transport.clone())
+    }
+
+    /// A new directory under the system's temporary one, removed when dropped.
+    pub struct TempDir(PathBuf);
+
+    pub fn temp_dir() -> TempDir {
+        let path = std::env::temp_dir().join(format!("cloudinary-{}", rand::random::<u64>()));
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    impl Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    impl SentRequest {
+        pub fn field(&self, name: &str) -> Option<&str> {
+            self.fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        }
+
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.get(name)?.to_str().ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::{self, FakeTransport};
+
+    #[tokio::test]
+    async fn sends_signed_requests() {
+        let transport = FakeTransport::default();
+        let cloudinary = fake::cloudinary(&transport);
+
+        let response = cloudinary.delete_image("sample").await.unwrap();
+        assert_eq!(response.result, "ok");
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].url,
+            "https://api.cloudinary.com/v1_1/cloud_name/image/destroy"
+        );
+        assert_eq!(requests[0].field("public_id"), Some("sample"));
+        assert_eq!(requests[0].field("api_key"), Some("123456789"));
+        assert!(requests[0].field("signature").is_some());
+    }
+}
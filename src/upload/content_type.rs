@@ -0,0 +1,138 @@
+use mime::Mime;
+use std::path::Path;
+
+/// Bytes read from the start of a file to detect its content type.
+pub(crate) const SNIFF_LENGTH: usize = 512;
+
+/// Detects the content type from the magic bytes, then from the file extension.
+pub(crate) fn detect_content_type(filename: &str, head: &[u8]) -> Option<Mime> {
+    from_magic_bytes(head)
+        .and_then(|content_type| content_type.parse().ok())
+        .or_else(|| mime_guess::from_path(Path::new(filename)).first())
+}
+
+fn from_magic_bytes(head: &[u8]) -> Option<&'static str> {
+    match head {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'B', b'M', ..] if is_bmp(head) => Some("image/bmp"),
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => Some("image/tiff"),
+        [0x00, 0x00, 0x01, 0x00, ..] if is_ico(head) => Some("image/x-icon"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'A', b'V', b'I', b' ', ..] => Some("video/x-msvideo"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Some("audio/wav"),
+        [_, _, _, _, b'f', b't', b'y', b'p', brand @ ..] => Some(from_iso_brand(brand)),
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => Some("video/webm"),
+        [b'O', b'g', b'g', b'S', ..] => Some("audio/ogg"),
+        [b'I', b'D', b'3', ..] => Some("audio/mpeg"),
+        [b'f', b'L', b'a', b'C', ..] => Some("audio/flac"),
+        [b'%', b'P', b'D', b'F', ..] => Some("application/pdf"),
+        [b'P', b'K', 0x03, 0x04, ..] => Some("application/zip"),
+        [0x1F, 0x8B, ..] => Some("application/gzip"),
+        _ if is_svg(head) => Some("image/svg+xml"),
+        _ => None,
+    }
+}
+
+/// Content type of an ISO base media file, from the major brand of its `ftyp` box.
+fn from_iso_brand(brand: &[u8]) -> &'static str {
+    match brand.get(..4) {
+        Some(b"heic" | b"heix" | b"heim" | b"heis") => "image/heic",
+        Some(b"mif1" | b"msf1") => "image/heif",
+        Some(b"avif" | b"avis") => "image/avif",
+        Some(b"qt  ") => "video/quicktime",
+        Some(b"M4A ") => "audio/mp4",
+        Some([b'3', b'g', ..]) => "video/3gpp",
+        _ => "video/mp4",
+    }
+}
+
+/// Whether the two-letter BMP signature is followed by a valid header:
+/// zero reserved bytes, then a DIB header of one of the known sizes.
+fn is_bmp(head: &[u8]) -> bool {
+    match head.get(6..18) {
+        Some([0, 0, 0, 0, _, _, _, _, size @ ..]) => matches!(
+            u32::from_le_bytes([size[0], size[1], size[2], size[3]]),
+            12 | 16 | 40 | 52 | 56 | 64 | 108 | 124
+        ),
+        _ => false,
+    }
+}
+
+/// Whether the ICO signature is followed by at least one image entry,
+/// with a zero reserved byte and 0 or 1 color planes.
+fn is_ico(head: &[u8]) -> bool {
+    match head.get(4..14) {
+        Some([count_low, count_high, _, _, _, 0, planes_low, 0, _, _]) => {
+            u16::from_le_bytes([*count_low, *count_high]) > 0 && *planes_low <= 1
+        }
+        _ => false,
+    }
+}
+
+fn is_svg(head: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    text.starts_with("<svg") || (text.starts_with("<?xml") && text.contains("<svg"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_content_type;
+
+    #[test]
+    fn magic_bytes() {
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        assert_eq!(
+            detect_content_type("image.jpg", &png).map(|mime| mime.to_string()),
+            Some("image/png".to_string())
+        );
+        let heic = *b"\x00\x00\x00\x18ftypheic\x00\x00\x00\x00";
+        assert_eq!(
+            detect_content_type("", &heic).map(|mime| mime.to_string()),
+            Some("image/heic".to_string())
+        );
+        let svg = b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>";
+        assert_eq!(
+            detect_content_type("", svg).map(|mime| mime.to_string()),
+            Some("image/svg+xml".to_string())
+        );
+    }
+
+    #[test]
+    fn weak_signatures() {
+        let mut bmp = b"BM\x36\x00\x0c\x00\x00\x00\x00\x00\x36\x00\x00\x00".to_vec();
+        bmp.extend_from_slice(&40u32.to_le_bytes());
+        assert_eq!(
+            detect_content_type("", &bmp).map(|mime| mime.to_string()),
+            Some("image/bmp".to_string())
+        );
+        let ico = b"\x00\x00\x01\x00\x01\x00\x10\x10\x00\x00\x01\x00\x20\x00";
+        assert_eq!(
+            detect_content_type("", ico).map(|mime| mime.to_string()),
+            Some("image/x-icon".to_string())
+        );
+
+        // Text starting like a bitmap or an icon keeps the type of its extension
+        assert_eq!(
+            detect_content_type("notes.txt", b"BMW service history, 2019 to 2023")
+                .map(|mime| mime.to_string()),
+            Some("text/plain".to_string())
+        );
+        assert_eq!(
+            detect_content_type("data.csv", b"\x00\x00\x01\x00\x00\x00a,b,c")
+                .map(|mime| mime.to_string()),
+            Some("text/csv".to_string())
+        );
+    }
+
+    #[test]
+    fn extension() {
+        assert_eq!(
+            detect_content_type("data.json", b"{}").map(|mime| mime.to_string()),
+            Some("application/json".to_string())
+        );
+        assert_eq!(detect_content_type("data", b"{}"), None);
+    }
+}
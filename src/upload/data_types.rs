@@ -8,6 +8,7 @@ use super::{
     delivery_type::DeliveryType, raw_convert::RawConvert, resource_type::ResourceTypes,
     responsive_breakpoints::ResponsiveBreakpoints,
 };
+use crate::transformation::Transformation;
 
 pub type Coordinates = [u32; 4];
 #[derive(Debug, Clone)]
@@ -28,6 +29,8 @@ pub enum DataType {
     RawConvert(RawConvert),
     VecOfString(Vec<String>),
     AllowedHeaders(HashMap<AllowedHeaders, String>),
+    Transformation(Transformation),
+    Transformations(Vec<Transformation>),
 }
 
 impl fmt::Display for DataType {
@@ -66,6 +69,8 @@ impl fmt::Display for DataType {
                 "{}",
                 value.iter().map(|(k, v)| format!("{k}: {v}")).join("\n")
             ),
+            DataType::Transformation(value) => write!(f, "{}", value),
+            DataType::Transformations(value) => write!(f, "{}", value.iter().join("|")),
         }
     }
 }
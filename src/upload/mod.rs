@@ -2,32 +2,44 @@ mod access_mode;
 mod allowed_headers;
 mod background_removal;
 mod categorizations;
+mod content_type;
 mod data_types;
 mod delivery_type;
+pub(crate) mod progress;
 mod raw_convert;
 mod resource_type;
 mod responsive_breakpoints;
+pub(crate) mod upload_source;
 
+use mime::Mime;
 use paste::paste;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
-use self::{
+use self::data_types::DataType;
+use self::progress::ProgressCallback;
+use crate::transformation::Transformation;
+
+pub use self::{
     access_mode::AccessModes, allowed_headers::AllowedHeaders,
     background_removal::BackgroundRemoval, categorizations::Categorizations,
-    data_types::Coordinates, data_types::DataType, delivery_type::DeliveryType,
+    data_types::Coordinates, delivery_type::DeliveryType, progress::UploadProgress,
     raw_convert::RawConvert, resource_type::ResourceTypes,
-    responsive_breakpoints::ResponsiveBreakpoints,
+    responsive_breakpoints::ResponsiveBreakpoints, upload_source::UploadSource,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UploadOptions<'entry_key_lifetime> {
     inner: BTreeMap<&'entry_key_lifetime str, DataType>,
+    progress: Option<ProgressCallback>,
+    content_type: Option<Mime>,
 }
 
-impl UploadOptions<'_> {
+impl<'entry_key_lifetime> UploadOptions<'entry_key_lifetime> {
     pub fn new() -> Self {
         Self {
             inner: BTreeMap::new(),
+            progress: None,
+            content_type: None,
         }
     }
     pub fn add_tags(mut self, tags: &[String]) -> Self {
@@ -134,6 +146,76 @@ impl UploadOptions<'_> {
         None
     }
 
+    /// Transformation applied to the asset before it is stored.
+    /// Strings such as `"c_fill,w_300"` are sent as is.
+    pub fn set_transformation(mut self, transformation: impl Into<Transformation>) -> Self {
+        self.inner.insert(
+            "transformation",
+            DataType::Transformation(transformation.into()),
+        );
+        self
+    }
+
+    pub fn get_transformation(&self) -> Option<Transformation> {
+        if let Some(DataType::Transformation(transformation)) = self.inner.get("transformation") {
+            return Some(transformation.clone());
+        }
+        None
+    }
+
+    /// Derived assets generated on upload, one per transformation.
+    /// Strings such as `"c_fill,w_300"` are sent as is.
+    pub fn set_eager<T: Into<Transformation>>(
+        mut self,
+        eager: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let eager = eager.into_iter().map(Into::into).collect();
+        self.inner.insert("eager", DataType::Transformations(eager));
+        self
+    }
+
+    pub fn get_eager(&self) -> Option<Vec<Transformation>> {
+        if let Some(DataType::Transformations(eager)) = self.inner.get("eager") {
+            return Some(eager.clone());
+        }
+        None
+    }
+
+    /// Calls `progress` as the file is sent. Not sent to Cloudinary.
+    ///
+    /// [`crate::Cloudinary::upload_large`] and resumable uploads call it once per
+    /// acknowledged chunk instead.
+    pub fn set_progress(
+        mut self,
+        progress: impl Fn(UploadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(ProgressCallback::new(progress));
+        self
+    }
+
+    pub(crate) fn get_progress(&self) -> Option<&ProgressCallback> {
+        self.progress.as_ref()
+    }
+
+    /// Content type of the uploaded file, instead of detecting it. Not sent to Cloudinary.
+    pub fn set_content_type(mut self, content_type: Mime) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    pub fn get_content_type(&self) -> Option<Mime> {
+        self.content_type.clone()
+    }
+
+    /// Options of this call, falling back to `defaults` for the ones not set.
+    pub(crate) fn with_defaults(&self, defaults: &UploadOptions<'entry_key_lifetime>) -> Self {
+        let mut options = defaults.clone();
+        options.inner.extend(self.inner.clone());
+        options.progress = self.progress.clone().or(options.progress);
+        options.content_type = self.content_type.clone().or(options.content_type);
+        options
+    }
+
     pub fn get_map(&self) -> BTreeMap<String, String> {
         self.inner.iter().fold(BTreeMap::new(), |mut acc, (k, v)| {
             acc.insert(k.to_string(), v.to_string());
@@ -157,7 +239,7 @@ macro_rules! add_field {
                     self
                 }
 
-                pub fn [<get_ $field>](self)->Option<$type>{
+                pub fn [<get_ $field>](&self)->Option<$type>{
                     if let Some($data_type(value)) = self.inner.get($field) {
                         return Some(value.clone());
                     }
@@ -224,7 +306,6 @@ add_field!(
 );
 add_field!(UploadOptions, "detection", String, DataType::String);
 add_field!(UploadOptions, "ocr", String, DataType::String);
-add_field!(UploadOptions, "eager", String, DataType::String);
 add_field!(UploadOptions, "eager_async", bool, DataType::Boolean);
 add_field!(
     UploadOptions,
@@ -232,7 +313,6 @@ add_field!(
     String,
     DataType::String
 );
-add_field!(UploadOptions, "transformation", String, DataType::String);
 add_field!(UploadOptions, "format", String, DataType::String);
 add_field!(
     UploadOptions,
@@ -283,6 +363,7 @@ add_field!(
 mod tests {
     use std::collections::HashSet;
 
+    use crate::transformation::{CropMode, Transformation};
     use crate::upload::access_mode::AccessModes;
     use crate::upload::delivery_type::DeliveryType;
 
@@ -294,6 +375,31 @@ mod tests {
         assert_eq!(params.get_folder(), Some("folder".to_string()));
     }
     #[test]
+    fn eager() {
+        let params = UploadOptions::new().set_eager(vec![
+            Transformation::new()
+                .set_crop(CropMode::Fill)
+                .set_width(300),
+            Transformation::new().set_width(100),
+        ]);
+        assert_eq!(
+            params.get_map().get("eager").map(String::as_str),
+            Some("c_fill,w_300|w_100")
+        );
+
+        let params = UploadOptions::new()
+            .set_eager(["w_400,h_300,c_pad", "w_260,h_200,c_crop"])
+            .set_transformation("a_90");
+        assert_eq!(
+            params.get_map().get("eager").map(String::as_str),
+            Some("w_400,h_300,c_pad|w_260,h_200,c_crop")
+        );
+        assert_eq!(
+            params.get_transformation(),
+            Some(Transformation::raw("a_90"))
+        );
+    }
+    #[test]
     fn upload_preset() {
         let params = UploadOptions::new().set_upload_preset("upload_preset".to_string());
         assert_eq!(
@@ -357,6 +463,17 @@ mod tests {
         assert_eq!(params.get_metadata(&"foo".to_string()), None);
     }
     #[test]
+    fn defaults() {
+        let defaults = UploadOptions::new()
+            .set_folder("default".to_string())
+            .set_overwrite(false);
+        let params = UploadOptions::new()
+            .set_folder("folder".to_string())
+            .with_defaults(&defaults);
+        assert_eq!(params.get_folder(), Some("folder".to_string()));
+        assert_eq!(params.get_overwrite(), Some(false));
+    }
+    #[test]
     fn auto_tagging() {
         let mut params = UploadOptions::new();
         params = params.add_auto_tagging(Some(0.5));
@@ -0,0 +1,197 @@
+use core::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Bytes of the file sent so far. The total is unknown for streams without a length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    pub total_bytes: Option<u64>,
+}
+
+#[derive(Clone)]
+pub(crate) struct ProgressCallback(Arc<dyn Fn(UploadProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    pub fn new(callback: impl Fn(UploadProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub fn report(&self, bytes_sent: u64, total_bytes: Option<u64>) {
+        (self.0)(UploadProgress {
+            bytes_sent,
+            total_bytes,
+        })
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Reports the bytes read from the inner reader, as they are handed to the HTTP client.
+pub(crate) struct ProgressReader {
+    reader: Pin<Box<dyn AsyncRead + Send>>,
+    progress: ProgressCallback,
+    bytes_sent: u64,
+    total_bytes: Option<u64>,
+}
+
+impl ProgressReader {
+    pub fn new(
+        reader: Pin<Box<dyn AsyncRead + Send>>,
+        progress: ProgressCallback,
+        total_bytes: Option<u64>,
+    ) -> Self {
+        Self {
+            reader,
+            progress,
+            bytes_sent: 0,
+            total_bytes,
+        }
+    }
+}
+
+impl AsyncRead for ProgressReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+        let poll = this.reader.as_mut().poll_read(cx, buf);
+        let read = buf.filled().len() - filled;
+        if read > 0 {
+            this.bytes_sent += read as u64;
+            this.progress.report(this.bytes_sent, this.total_bytes);
+        }
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::AsyncReadExt;
+
+    use super::{ProgressCallback, ProgressReader, UploadProgress};
+    use crate::transport::fake::{cloudinary, temp_dir, FakeTransport};
+    use crate::upload::{UploadOptions, UploadSource};
+
+    type Reports = Arc<Mutex<Vec<UploadProgress>>>;
+
+    fn record(reports: &Reports) -> impl Fn(UploadProgress) + Send + Sync + 'static {
+        let reports = reports.clone();
+        move |progress| reports.lock().unwrap().push(progress)
+    }
+
+    fn progress(bytes_sent: u64, total_bytes: Option<u64>) -> UploadProgress {
+        UploadProgress {
+            bytes_sent,
+            total_bytes,
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_bytes_read() {
+        let reports = Reports::default();
+        let callback = ProgressCallback::new(record(&reports));
+
+        let mut reader =
+            ProgressReader::new(Box::pin(Cursor::new(vec![0u8; 10])), callback, Some(10));
+        let mut buffer = [0u8; 4];
+        while reader.read(&mut buffer).await.unwrap() > 0 {}
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports.last(), Some(&progress(10, Some(10))));
+    }
+
+    #[tokio::test]
+    async fn reports_uploads() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+        let reports = Reports::default();
+
+        let options = UploadOptions::new().set_progress(record(&reports));
+        cloudinary
+            .upload(UploadSource::bytes(vec![0u8; 1000], "data.bin"), &options)
+            .await
+            .unwrap();
+
+        let reports = reports.lock().unwrap();
+        assert!(reports
+            .windows(2)
+            .all(|pair| pair[0].bytes_sent < pair[1].bytes_sent));
+        assert!(reports
+            .iter()
+            .all(|report| report.total_bytes == Some(1000)));
+        assert_eq!(reports.last(), Some(&progress(1000, Some(1000))));
+    }
+
+    #[tokio::test]
+    async fn reports_acknowledged_chunks() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+        let reports = Reports::default();
+
+        let options = UploadOptions::new().set_progress(record(&reports));
+        cloudinary
+            .upload_large(UploadSource::bytes(vec![0u8; 10], "video.mp4"), &options, 4)
+            .await
+            .unwrap();
+        let source = UploadSource::stream(Cursor::new(vec![0u8; 10]), "video.mp4", None);
+        cloudinary.upload_large(source, &options, 4).await.unwrap();
+
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                progress(4, Some(10)),
+                progress(8, Some(10)),
+                progress(10, Some(10)),
+                progress(4, None),
+                progress(8, None),
+                progress(10, Some(10)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_resumed_chunks() {
+        let directory = temp_dir();
+        let file_path = directory.join("video.mp4");
+        let journal_path = directory.join("video.journal");
+        std::fs::write(&file_path, vec![0u8; 100]).unwrap();
+
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+        let reports = Reports::default();
+
+        let options = UploadOptions::new().set_progress(record(&reports));
+        let mut upload = cloudinary
+            .start_resumable_upload(&file_path, &options, 30, &journal_path)
+            .await
+            .unwrap();
+        transport.respond(200, "{}");
+        transport.respond(200, "{}");
+        transport.respond(500, "Internal Server Error");
+        assert!(upload.resume().await.is_err());
+        upload.resume().await.unwrap();
+
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                progress(30, Some(100)),
+                progress(60, Some(100)),
+                progress(90, Some(100)),
+                progress(100, Some(100)),
+            ]
+        );
+    }
+}
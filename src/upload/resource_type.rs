@@ -1,6 +1,8 @@
 use core::fmt;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ResourceTypes {
     Image,
     Raw,
@@ -18,3 +20,14 @@ impl fmt::Display for ResourceTypes {
         }
     }
 }
+
+impl ResourceTypes {
+    /// Content type of the uploaded file part when it cannot be detected.
+    pub(crate) fn content_type(&self) -> &'static str {
+        match self {
+            ResourceTypes::Image => "image/*",
+            ResourceTypes::Video => "video/*",
+            ResourceTypes::Raw | ResourceTypes::Auto => "application/octet-stream",
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use bytes::Bytes;
+use core::fmt;
+use std::io::Cursor;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tokio::fs::File;
+use tokio::io::{self, AsyncRead, AsyncReadExt};
+
+use super::content_type::{detect_content_type, SNIFF_LENGTH};
+use super::progress::{ProgressCallback, ProgressReader};
+use super::{ResourceTypes, UploadOptions};
+use crate::transport::{FilePart, HttpRequest};
+use crate::CloudinaryError;
+
+/// Where the uploaded file comes from.
+///
+/// Local files and in-memory data are streamed as a file part, while remote
+/// sources are passed as the `file` parameter for Cloudinary to fetch.
+pub enum UploadSource {
+    /// A file on the local filesystem.
+    Path(PathBuf),
+    /// In-memory data with the filename reported to Cloudinary.
+    Bytes { data: Bytes, filename: String },
+    /// An HTTP(S) URL or a storage bucket reference such as `s3://` or `gs://`.
+    Url(String),
+    /// A base64 data URI, e.g. `data:image/png;base64,...`.
+    DataUri(String),
+    /// Any reader, streamed without buffering. The length is sent when known.
+    Stream {
+        reader: Pin<Box<dyn AsyncRead + Send>>,
+        filename: String,
+        length: Option<u64>,
+    },
+}
+
+impl UploadSource {
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        UploadSource::Path(path.into())
+    }
+
+    pub fn bytes(data: impl Into<Bytes>, filename: &str) -> Self {
+        UploadSource::Bytes {
+            data: data.into(),
+            filename: filename.to_string(),
+        }
+    }
+
+    pub fn url(url: &str) -> Self {
+        UploadSource::Url(url.to_string())
+    }
+
+    pub fn data_uri(data_uri: &str) -> Self {
+        UploadSource::DataUri(data_uri.to_string())
+    }
+
+    pub fn stream(
+        reader: impl AsyncRead + Send + 'static,
+        filename: &str,
+        length: Option<u64>,
+    ) -> Self {
+        UploadSource::Stream {
+            reader: Box::pin(reader),
+            filename: filename.to_string(),
+            length,
+        }
+    }
+
+    /// Whether Cloudinary fetches the file itself.
+    pub(crate) fn is_remote(&self) -> bool {
+        matches!(self, UploadSource::Url(_) | UploadSource::DataUri(_))
+    }
+
+    /// Opens a local source as a reader.
+    pub(crate) async fn into_reader(self) -> Result<SourceReader, CloudinaryError> {
+        match self {
+            UploadSource::Path(path) => {
+                let file = File::open(&path).await?;
+                let length = file.metadata().await?.len();
+                Ok(SourceReader {
+                    reader: Box::pin(file),
+                    filename: file_name(&path)?,
+                    length: Some(length),
+                })
+            }
+            UploadSource::Bytes { data, filename } => Ok(SourceReader {
+                length: Some(data.len() as u64),
+                reader: Box::pin(Cursor::new(data)),
+                filename,
+            }),
+            UploadSource::Stream {
+                reader,
+                filename,
+                length,
+            } => Ok(SourceReader {
+                reader,
+                filename,
+                length,
+            }),
+            UploadSource::Url(_) | UploadSource::DataUri(_) => Err(CloudinaryError::Configuration(
+                String::from("Remote sources cannot be read locally."),
+            )),
+        }
+    }
+
+    /// Whether the source can be read again to retry a failed request.
+    pub(crate) fn is_replayable(&self) -> bool {
+        !matches!(self, UploadSource::Stream { .. })
+    }
+
+    /// Content type of the file: the override set in the options, else detected
+    /// from the content and filename, else the default of the resource type.
+    pub(crate) async fn content_type(
+        &mut self,
+        resource_type: &ResourceTypes,
+        options: &UploadOptions<'_>,
+    ) -> Result<String, CloudinaryError> {
+        if let Some(content_type) = options.get_content_type() {
+            return Ok(content_type.to_string());
+        }
+
+        let (filename, head) = match self {
+            UploadSource::Path(path) => {
+                let mut file = File::open(&path).await?;
+                (file_name(path)?, read_chunk(&mut file, SNIFF_LENGTH).await?)
+            }
+            UploadSource::Bytes { data, filename } => {
+                (filename.clone(), data.slice(..data.len().min(SNIFF_LENGTH)))
+            }
+            UploadSource::Stream {
+                reader, filename, ..
+            } => (filename.clone(), peek(reader, SNIFF_LENGTH).await?),
+            UploadSource::Url(_) | UploadSource::DataUri(_) => (String::new(), Bytes::new()),
+        };
+
+        Ok(detect_content_type(&filename, &head).map_or_else(
+            || resource_type.content_type().to_string(),
+            |content_type| content_type.to_string(),
+        ))
+    }
+
+    /// The source to send in one attempt: a copy of the source, read from the start
+    /// again, except for streams which can only be sent once.
+    pub(crate) fn for_attempt(&mut self) -> Self {
+        match self {
+            UploadSource::Path(path) => UploadSource::Path(path.clone()),
+            UploadSource::Bytes { data, filename } => UploadSource::Bytes {
+                data: data.clone(),
+                filename: filename.clone(),
+            },
+            UploadSource::Url(url) => UploadSource::Url(url.clone()),
+            UploadSource::DataUri(data_uri) => UploadSource::DataUri(data_uri.clone()),
+            UploadSource::Stream {
+                reader,
+                filename,
+                length,
+            } => UploadSource::Stream {
+                reader: mem::replace(reader, Box::pin(io::empty())),
+                filename: filename.clone(),
+                length: *length,
+            },
+        }
+    }
+
+    /// Adds the `file` field to a request. Local sources are streamed,
+    /// while remote ones are passed for Cloudinary to fetch.
+    pub(crate) async fn attach(
+        self,
+        mut request: HttpRequest,
+        content_type: &str,
+        progress: Option<&ProgressCallback>,
+    ) -> Result<HttpRequest, CloudinaryError> {
+        match self {
+            UploadSource::Url(value) | UploadSource::DataUri(value) => {
+                request.fields.push((String::from("file"), value));
+            }
+            source => {
+                let source = source.into_reader().await?;
+                request.file = Some(source.into_file(content_type, progress));
+            }
+        }
+        Ok(request)
+    }
+}
+
+impl fmt::Debug for UploadSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UploadSource::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            UploadSource::Bytes { data, filename } => f
+                .debug_struct("Bytes")
+                .field("data", &format_args!("{} bytes", data.len()))
+                .field("filename", filename)
+                .finish(),
+            UploadSource::Url(url) => f.debug_tuple("Url").field(url).finish(),
+            UploadSource::DataUri(_) => f.debug_tuple("DataUri").finish(),
+            UploadSource::Stream {
+                filename, length, ..
+            } => f
+                .debug_struct("Stream")
+                .field("filename", filename)
+                .field("length", length)
+                .finish(),
+        }
+    }
+}
+
+pub(crate) struct SourceReader {
+    pub reader: Pin<Box<dyn AsyncRead + Send>>,
+    pub filename: String,
+    pub length: Option<u64>,
+}
+
+impl SourceReader {
+    /// Streams the reader as the file of a request.
+    fn into_file(self, content_type: &str, progress: Option<&ProgressCallback>) -> FilePart {
+        let reader: Pin<Box<dyn AsyncRead + Send>> = match progress {
+            Some(progress) => Box::pin(ProgressReader::new(
+                self.reader,
+                progress.clone(),
+                self.length,
+            )),
+            None => self.reader,
+        };
+
+        FilePart {
+            reader,
+            filename: self.filename,
+            content_type: content_type.to_string(),
+            length: self.length,
+        }
+    }
+}
+
+/// Reads the start of a reader without consuming it.
+async fn peek(
+    reader: &mut Pin<Box<dyn AsyncRead + Send>>,
+    length: usize,
+) -> Result<Bytes, CloudinaryError> {
+    let head = read_chunk(reader, length).await?;
+    let rest = mem::replace(reader, Box::pin(io::empty()));
+    *reader = Box::pin(Cursor::new(head.clone()).chain(rest));
+    Ok(head)
+}
+
+pub(crate) fn file_name(file_path: &Path) -> Result<String, CloudinaryError> {
+    Ok(file_path
+        .file_name()
+        .ok_or_else(|| CloudinaryError::Configuration(String::from("Missing filename")))?
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Reads up to `chunk_size` bytes, returning fewer only at the end of the source.
+pub(crate) async fn read_chunk(
+    reader: &mut (impl AsyncRead + Unpin),
+    chunk_size: usize,
+) -> Result<Bytes, CloudinaryError> {
+    let mut buffer = vec![0; chunk_size];
+    let mut filled = 0;
+    while filled < chunk_size {
+        let read = reader.read(&mut buffer[filled..]).await?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    buffer.truncate(filled);
+    Ok(Bytes::from(buffer))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{read_chunk, UploadSource};
+    use crate::transport::fake::{cloudinary, FakeTransport};
+    use crate::upload::UploadOptions;
+    use crate::SignatureAlgorithm;
+
+    const PNG: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+    #[tokio::test]
+    async fn read_chunks() {
+        let mut reader = Cursor::new(vec![1u8; 10]);
+        assert_eq!(read_chunk(&mut reader, 4).await.unwrap().len(), 4);
+        assert_eq!(read_chunk(&mut reader, 4).await.unwrap().len(), 4);
+        assert_eq!(read_chunk(&mut reader, 4).await.unwrap().len(), 2);
+        assert!(read_chunk(&mut reader, 4).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn uploads_bytes() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+
+        let data = [&PNG[..], &[0u8; 100]].concat();
+        cloudinary
+            .upload(
+                UploadSource::bytes(data.clone(), "logo"),
+                &UploadOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        let file = requests[0].file.as_ref().unwrap();
+        assert_eq!(file.filename, "logo");
+        assert_eq!(file.content_type, "image/png");
+        assert_eq!(file.length, Some(108));
+        assert_eq!(file.data, data);
+    }
+
+    #[tokio::test]
+    async fn sends_remote_sources_as_fields() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+
+        let url = "https://example.com/sample.jpg";
+        let data_uri = "data:image/png;base64,iVBORw0KGgo=";
+        for source in [UploadSource::url(url), UploadSource::data_uri(data_uri)] {
+            cloudinary
+                .upload(source, &UploadOptions::new())
+                .await
+                .unwrap();
+        }
+
+        let requests = transport.requests();
+        for (request, file) in requests.iter().zip([url, data_uri]) {
+            assert!(request.file.is_none());
+            assert_eq!(request.field("file"), Some(file));
+            // Signed without the file
+            let timestamp = request.field("timestamp").unwrap();
+            assert_eq!(
+                request.field("signature"),
+                Some(
+                    SignatureAlgorithm::Sha1
+                        .sign(&format!("timestamp={timestamp}"), "api_secret")
+                        .as_str()
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn uploads_streams_whole() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+
+        // Longer than the head read to detect the content type
+        let data: Vec<u8> = PNG
+            .iter()
+            .copied()
+            .chain((0..1000).map(|i| i as u8))
+            .collect();
+        for length in [Some(1008), None] {
+            let reader = Cursor::new(data.clone());
+            cloudinary
+                .upload(
+                    UploadSource::stream(reader, "logo", length),
+                    &UploadOptions::new(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let requests = transport.requests();
+        let files: Vec<_> = requests
+            .iter()
+            .map(|request| request.file.as_ref().unwrap())
+            .collect();
+        assert_eq!(files[0].length, Some(1008));
+        assert_eq!(files[1].length, None);
+        for file in files {
+            assert_eq!(file.filename, "logo");
+            assert_eq!(file.content_type, "image/png");
+            assert_eq!(file.data, data);
+        }
+    }
+}
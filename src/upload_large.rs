@@ -0,0 +1,253 @@
+use bytes::Bytes;
+use reqwest::header::HeaderValue;
+use std::collections::BTreeMap;
+use std::future;
+use std::io::Cursor;
+
+use crate::result::UploadResponse;
+use crate::transport::{FilePart, HttpResponse};
+use crate::upload::upload_source::read_chunk;
+use crate::upload::{ResourceTypes, UploadOptions, UploadSource};
+use crate::{parse_response, upload_params, ApiResponse, Cloudinary, CloudinaryError};
+
+/// Chunk size used by the official SDKs, 20 MB.
+pub const DEFAULT_CHUNK_SIZE: usize = 20_000_000;
+
+/// Largest chunk size accepted, 1 GB, as each chunk is buffered in memory before it is sent.
+pub const MAX_CHUNK_SIZE: usize = 1_000_000_000;
+
+const HEADER_UNIQUE_UPLOAD_ID: &str = "X-Unique-Upload-Id";
+const HEADER_CONTENT_RANGE: &str = "Content-Range";
+
+impl Cloudinary {
+    /// Uploads a large file in chunks of `chunk_size` bytes, as required for files over 100 MB.
+    /// `chunk_size` must be at most [`MAX_CHUNK_SIZE`]. Cloudinary also rejects chunks
+    /// smaller than 5 MB, except for the last one.
+    /// Remote sources are fetched by Cloudinary and uploaded in a single request.
+    /// ```rust,no_run
+    /// use cloudinary::{Cloudinary, DEFAULT_CHUNK_SIZE};
+    /// use cloudinary::upload::{ResourceTypes, UploadOptions, UploadSource};
+    /// let cloudinary = Cloudinary::new("cloud_name", 123456789, "api_secret");
+    /// let options = UploadOptions::new().set_resource_type(ResourceTypes::Video);
+    /// let result = cloudinary.upload_large(UploadSource::path("./video.mp4"), &options, DEFAULT_CHUNK_SIZE);
+    /// ```
+    pub async fn upload_large(
+        &self,
+        source: UploadSource,
+        options: &UploadOptions<'_>,
+        chunk_size: usize,
+    ) -> Result<UploadResponse, CloudinaryError> {
+        self.upload_large_with_response(source, options, chunk_size)
+            .await
+            .map(ApiResponse::into_result)
+    }
+
+    /// Like [`Cloudinary::upload_large`], along with the status and headers of the last response.
+    pub async fn upload_large_with_response(
+        &self,
+        mut source: UploadSource,
+        options: &UploadOptions<'_>,
+        chunk_size: usize,
+    ) -> Result<ApiResponse<UploadResponse>, CloudinaryError> {
+        check_chunk_size(chunk_size)?;
+
+        let options = &options.with_defaults(&self.default_options);
+        if source.is_remote() {
+            return self.upload_with_response(source, options).await;
+        }
+
+        let resource_type = options.get_resource_type().unwrap_or(ResourceTypes::Image);
+        let content_type = source.content_type(&resource_type, options).await?;
+        let mut source = source.into_reader().await?;
+        let params = upload_params(options);
+        let unique_upload_id = new_unique_upload_id();
+
+        let mut chunk = read_chunk(&mut source.reader, chunk_size).await?;
+        if chunk.is_empty() {
+            return Err(CloudinaryError::Configuration(String::from(
+                "Upload source is empty.",
+            )));
+        }
+
+        let mut start: u64 = 0;
+        loop {
+            // Read ahead to know whether the current chunk is the last one
+            let next_chunk = if chunk.len() == chunk_size {
+                read_chunk(&mut source.reader, chunk_size).await?
+            } else {
+                Bytes::new()
+            };
+            let is_last = next_chunk.is_empty();
+
+            let chunk_length = chunk.len() as u64;
+            let end = start + chunk_length - 1;
+            let total = if is_last {
+                (start + chunk_length).to_string()
+            } else {
+                source
+                    .length
+                    .map_or_else(|| String::from("-1"), |length| length.to_string())
+            };
+
+            let part = Chunk {
+                data: chunk,
+                filename: &source.filename,
+                content_type: &content_type,
+                content_range: format!("bytes {start}-{end}/{total}"),
+            };
+            let response = self
+                .upload_chunk(&part, &resource_type, &params, &unique_upload_id)
+                .await?;
+
+            if let Some(progress) = options.get_progress() {
+                let bytes_sent = start + chunk_length;
+                progress.report(bytes_sent, source.length.or(is_last.then_some(bytes_sent)));
+            }
+
+            // Intermediate chunks only acknowledge the received range
+            if is_last {
+                return parse_response(response);
+            }
+
+            start += chunk_length;
+            chunk = next_chunk;
+        }
+    }
+
+    /// Sends one chunk, retrying it as allowed by the retry policy.
+    pub(crate) async fn upload_chunk(
+        &self,
+        chunk: &Chunk<'_>,
+        resource_type: &ResourceTypes,
+        params: &BTreeMap<String, String>,
+        unique_upload_id: &str,
+    ) -> Result<HttpResponse, CloudinaryError> {
+        let endpoint = self.upload_endpoint(resource_type);
+        let unique_upload_id = header_value(unique_upload_id)?;
+        let content_range = header_value(&chunk.content_range)?;
+        let request = || {
+            let mut request = self.post(endpoint.clone(), params)?;
+            request
+                .headers
+                .insert(HEADER_UNIQUE_UPLOAD_ID, unique_upload_id.clone());
+            request
+                .headers
+                .insert(HEADER_CONTENT_RANGE, content_range.clone());
+            request.file = Some(FilePart {
+                reader: Box::pin(Cursor::new(chunk.data.clone())),
+                filename: chunk.filename.to_string(),
+                content_type: chunk.content_type.to_string(),
+                length: Some(chunk.data.len() as u64),
+            });
+            Ok(request)
+        };
+        self.send(true, || future::ready(request())).await
+    }
+}
+
+/// A part of the file sent by a chunked upload.
+pub(crate) struct Chunk<'a> {
+    pub data: Bytes,
+    pub filename: &'a str,
+    pub content_type: &'a str,
+    pub content_range: String,
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, CloudinaryError> {
+    HeaderValue::from_str(value)
+        .map_err(|_| CloudinaryError::Configuration(format!("Invalid header value: {value}")))
+}
+
+pub(crate) fn check_chunk_size(chunk_size: usize) -> Result<(), CloudinaryError> {
+    if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+        return Err(CloudinaryError::Configuration(format!(
+            "Chunk size must be between 1 and {MAX_CHUNK_SIZE} bytes."
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn new_unique_upload_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::MAX_CHUNK_SIZE;
+    use crate::transport::fake::{cloudinary, FakeTransport};
+    use crate::upload::{UploadOptions, UploadSource};
+    use crate::CloudinaryError;
+
+    #[tokio::test]
+    async fn uploads_chunks() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+        let data: Vec<u8> = (0..10).collect();
+
+        cloudinary
+            .upload_large(
+                UploadSource::bytes(data.clone(), "video.mp4"),
+                &UploadOptions::new(),
+                4,
+            )
+            .await
+            .unwrap();
+        // The last chunk fills the chunk size, and the length of the stream is unknown
+        cloudinary
+            .upload_large(
+                UploadSource::stream(Cursor::new(data[..8].to_vec()), "video.mp4", None),
+                &UploadOptions::new(),
+                4,
+            )
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        let ranges: Vec<_> = requests
+            .iter()
+            .map(|request| request.header("Content-Range").unwrap())
+            .collect();
+        assert_eq!(
+            ranges,
+            [
+                "bytes 0-3/10",
+                "bytes 4-7/10",
+                "bytes 8-9/10",
+                "bytes 0-3/-1",
+                "bytes 4-7/8",
+            ]
+        );
+
+        let upload_ids: Vec<_> = requests
+            .iter()
+            .map(|request| request.header("X-Unique-Upload-Id").unwrap())
+            .collect();
+        assert!(upload_ids[..3].iter().all(|id| *id == upload_ids[0]));
+        assert!(upload_ids[3..].iter().all(|id| *id == upload_ids[3]));
+        assert_ne!(upload_ids[0], upload_ids[3]);
+
+        let sent: Vec<u8> = requests[..3]
+            .iter()
+            .flat_map(|request| request.file.as_ref().unwrap().data.clone())
+            .collect();
+        assert_eq!(sent, data);
+    }
+
+    #[tokio::test]
+    async fn rejects_chunk_sizes() {
+        let transport = FakeTransport::default();
+        let cloudinary = cloudinary(&transport);
+
+        for chunk_size in [0, MAX_CHUNK_SIZE + 1, usize::MAX] {
+            let source = UploadSource::bytes(vec![0u8; 10], "video.mp4");
+            let error = cloudinary
+                .upload_large(source, &UploadOptions::new(), chunk_size)
+                .await
+                .err();
+            assert!(matches!(error, Some(CloudinaryError::Configuration(_))));
+        }
+        assert!(transport.requests().is_empty());
+    }
+}